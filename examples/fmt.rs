@@ -0,0 +1,48 @@
+//! Formats question files in place, or checks whether they are formatted.
+//!
+//! ```text
+//! cargo run --example fmt -- [--check] [--wrap <width>] <file>...
+//! ```
+use md_questions::{format_with, FormatOptions, Wrap};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::process::ExitCode;
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let mut check = false;
+    let mut options = FormatOptions::default();
+    let mut files = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--wrap" => {
+                let width = args.next().ok_or("missing width after --wrap")?.parse()?;
+                options = options.wrap(Wrap::At(width));
+            }
+            _ => files.push(arg),
+        }
+    }
+
+    let mut unformatted = false;
+    for file in files {
+        let content = fs::read_to_string(&file)?;
+        let formatted = format_with(&content, &options).map_err(|e| format!("{file}: {e}"))?;
+        if formatted == content {
+            continue;
+        }
+        if check {
+            println!("{file} is not formatted");
+            unformatted = true;
+        } else {
+            fs::write(&file, formatted)?;
+        }
+    }
+
+    Ok(if unformatted {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
@@ -1,12 +1,26 @@
-use getset::CopyGetters;
+use crate::markdown::Markdown;
+use crate::span::{Location, Span};
 
-#[derive(Default, Debug, CopyGetters, Eq, PartialEq, Clone)]
+use getset::{CopyGetters, Getters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const KEY_POINT_MARKER: &str = "[key]";
+
+#[derive(Default, Debug, Getters, CopyGetters, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClosedAnswer {
     #[getset(get = "pub")]
-    text: String,
+    pub(crate) text: String,
 
     #[getset(get_copy = "pub")]
     is_correct: bool,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) explanation: Option<String>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    location: Location<Span>,
 }
 
 impl ClosedAnswer {
@@ -14,6 +28,8 @@ impl ClosedAnswer {
         Self {
             text: text.into(),
             is_correct,
+            explanation: None,
+            location: Location::default(),
         }
     }
 
@@ -24,15 +40,105 @@ impl ClosedAnswer {
     pub fn correct<S: Into<String>>(text: S) -> Self {
         Self::new(text, true)
     }
+
+    /// Attaches explanation why the answer is correct or not.
+    #[must_use]
+    pub fn with_explanation<S: Into<String>>(mut self, explanation: S) -> Self {
+        self.explanation = Some(explanation.into());
+        self
+    }
+
+    /// Explanation written in the blockquote under the answer.
+    #[must_use]
+    pub fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    /// Answer text as [`Markdown`].
+    #[must_use]
+    pub fn markdown(&self) -> Markdown<'_> {
+        Markdown::new(&self.text)
+    }
+
+    /// Span of the answer line, from the checkbox up to the end of the text. Available only for
+    /// parsed answers.
+    #[must_use]
+    pub fn span(&self) -> Option<Span> {
+        self.location.get().copied()
+    }
+
+    pub(crate) fn located(mut self, span: Span) -> Self {
+        self.location = Location::new(span);
+        self
+    }
 }
 
-#[derive(Default, Debug, Eq, PartialEq, Clone)]
+#[derive(Default, Debug, Getters, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OpenAnswer {
-    text: String,
+    #[getset(get = "pub")]
+    pub(crate) text: String,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    location: Location<Span>,
 }
 
 impl OpenAnswer {
     pub fn new<S: Into<String>>(text: S) -> Self {
-        Self { text: text.into() }
+        Self {
+            text: text.into(),
+            location: Location::default(),
+        }
+    }
+
+    /// Answer as [`Markdown`].
+    #[must_use]
+    pub fn markdown(&self) -> Markdown<'_> {
+        Markdown::new(&self.text)
+    }
+
+    /// Items of the lists in the answer without the list markers and the key point marker, e.g.
+    /// `["Tree", "Rooted Tree"]` for `1. Tree` and `2. [key] Rooted Tree`.
+    #[must_use]
+    pub fn items(&self) -> Vec<String> {
+        self.markdown()
+            .list_items()
+            .into_iter()
+            .map(|item| match key_point(&item) {
+                Some(text) => text.to_string(),
+                None => item,
+            })
+            .collect()
     }
+
+    /// Items marked with `[key]`, which should be mentioned in the answer to consider it
+    /// correct.
+    #[must_use]
+    pub fn key_points(&self) -> Vec<String> {
+        self.markdown()
+            .list_items()
+            .iter()
+            .filter_map(|item| key_point(item))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Span of the answer text. Available only for parsed answers.
+    #[must_use]
+    pub fn span(&self) -> Option<Span> {
+        self.location.get().copied()
+    }
+
+    pub(crate) fn located(mut self, span: Span) -> Self {
+        self.location = Location::new(span);
+        self
+    }
+}
+
+// Text of the item marked as a key point with case-insensitive `[key]`.
+fn key_point(item: &str) -> Option<&str> {
+    let marker = item.get(..KEY_POINT_MARKER.len())?;
+    marker
+        .eq_ignore_ascii_case(KEY_POINT_MARKER)
+        .then(|| item[KEY_POINT_MARKER.len()..].trim_start())
 }
@@ -0,0 +1,93 @@
+use crate::span::Span;
+
+use getset::{CopyGetters, Getters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Problem found in the markdown which didn't stop the parsing, or reported by a lint rule.
+#[derive(Debug, Getters, CopyGetters, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Diagnostic {
+    #[getset(get_copy = "pub")]
+    severity: Severity,
+
+    /// Location in the markdown. Problems found in questions which weren't parsed, e.g. built in
+    /// code, have no location.
+    #[getset(get_copy = "pub")]
+    span: Option<Span>,
+
+    #[getset(get = "pub")]
+    message: String,
+
+    #[getset(skip)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    rule: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new<P: Into<Option<Span>>, S: Into<String>>(
+        severity: Severity,
+        span: P,
+        message: S,
+    ) -> Self {
+        Self {
+            severity,
+            span: span.into(),
+            message: message.into(),
+            rule: None,
+        }
+    }
+
+    pub fn error<P: Into<Option<Span>>, S: Into<String>>(span: P, message: S) -> Self {
+        Self::new(Severity::Error, span, message)
+    }
+
+    pub fn warning<P: Into<Option<Span>>, S: Into<String>>(span: P, message: S) -> Self {
+        Self::new(Severity::Warning, span, message)
+    }
+
+    /// Name of the lint rule which reported the problem. Parse errors have no rule.
+    #[must_use]
+    pub fn rule(&self) -> Option<&str> {
+        self.rule.as_deref()
+    }
+
+    pub(crate) fn reported_by<S: Into<String>>(mut self, rule: S) -> Self {
+        self.rule = Some(rule.into());
+        self
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(span) = self.span {
+            write!(f, "{}:{}: ", span.start().line(), span.start().column())?;
+        }
+        write!(f, "{}", self.severity)?;
+        if let Some(rule) = &self.rule {
+            write!(f, "[{rule}]")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
@@ -0,0 +1,410 @@
+//! Detection of near-duplicate questions.
+//!
+//! Questions are compared by the Jaccard similarity of word shingles (sequences of consecutive
+//! words) of their normalised texts and answers. The order of answers doesn't matter. Questions
+//! similar enough are grouped into [`Cluster`]s:
+//!
+//! ```
+//! use md_questions::duplicates::DuplicateFinder;
+//! use md_questions::MdQuestions;
+//!
+//! let questions = MdQuestions::parse("")?;
+//! for cluster in DuplicateFinder::default().threshold(0.8).find(&questions) {
+//!     let numbers = cluster.members().iter().map(|m| m.number()).collect::<Vec<_>>();
+//!     println!("{numbers:?} are {:.0}% similar", cluster.similarity() * 100.0);
+//! }
+//! # Ok::<(), md_questions::ParseError>(())
+//! ```
+
+use crate::question::Question;
+use crate::MdQuestions;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Finds groups of similar questions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateFinder {
+    threshold: f64,
+    shingle_size: usize,
+}
+
+impl Default for DuplicateFinder {
+    fn default() -> Self {
+        Self {
+            threshold: 0.7,
+            shingle_size: 3,
+        }
+    }
+}
+
+impl DuplicateFinder {
+    /// Sets the lowest similarity, from 0 to 1, of questions considered duplicates. It's 0.7 by
+    /// default.
+    #[must_use]
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the number of consecutive words compared together. It's 3 by default. Smaller
+    /// shingles find questions with reordered words, bigger ones require longer common phrases.
+    #[must_use]
+    pub fn shingle_size(mut self, shingle_size: usize) -> Self {
+        self.shingle_size = shingle_size.max(1);
+        self
+    }
+
+    /// Similarity of two questions, from 0 (nothing in common) to 1 (the same words).
+    #[must_use]
+    pub fn similarity(&self, a: &Question, b: &Question) -> f64 {
+        jaccard(&self.shingles(a), &self.shingles(b))
+    }
+
+    /// Clusters of similar questions of a single question bank.
+    #[must_use]
+    pub fn find(&self, questions: &MdQuestions) -> Vec<Cluster> {
+        self.find_across(&[questions])
+    }
+
+    /// Clusters of similar questions of many question banks, e.g. parsed from many files.
+    /// [`Member::bank`] is the index of the question bank in `banks`.
+    #[must_use]
+    pub fn find_across(&self, banks: &[&MdQuestions]) -> Vec<Cluster> {
+        let questions = banks
+            .iter()
+            .enumerate()
+            .flat_map(|(bank, questions)| {
+                questions.iter().enumerate().map(move |(index, question)| {
+                    let member = Member {
+                        bank,
+                        index,
+                        number: question.number(),
+                    };
+                    (member, question)
+                })
+            })
+            .collect::<Vec<_>>();
+        let shingles = questions
+            .iter()
+            .map(|(_, question)| self.shingles(question))
+            .collect::<Vec<_>>();
+        let mut groups = Groups::new(questions.len());
+        for a in 0..questions.len() {
+            for b in a + 1..questions.len() {
+                let similarity = jaccard(&shingles[a], &shingles[b]);
+                if similarity >= self.threshold {
+                    groups.join(a, b, similarity);
+                }
+            }
+        }
+        groups
+            .clusters()
+            .into_iter()
+            .map(|(members, similarity)| Cluster {
+                members: members.iter().map(|&idx| questions[idx].0).collect(),
+                similarity,
+            })
+            .collect()
+    }
+
+    // Hashes of word shingles of the question text and of each answer. Texts shorter than the
+    // shingle size are a single shingle.
+    fn shingles(&self, question: &Question) -> Vec<u64> {
+        let mut texts = vec![question.text()];
+        match question {
+            Question::Closed(q) => texts.extend(q.answers().iter().map(|a| a.text().as_str())),
+            Question::Open(q) => texts.push(q.answer().text()),
+        }
+        let mut shingles = Vec::new();
+        for (field, text) in texts.into_iter().enumerate() {
+            // Answers are compared with answers regardless of their order, but not with the text.
+            let field = field.min(1);
+            let words = words(text);
+            let size = self.shingle_size.min(words.len()).max(1);
+            shingles.extend(words.windows(size).map(|shingle| {
+                let mut hasher = DefaultHasher::new();
+                (field, shingle).hash(&mut hasher);
+                hasher.finish()
+            }));
+        }
+        shingles.sort_unstable();
+        shingles.dedup();
+        shingles
+    }
+}
+
+/// Group of similar questions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cluster {
+    members: Vec<Member>,
+    similarity: f64,
+}
+
+impl Cluster {
+    /// Similar questions in the order they appear in the question banks.
+    #[must_use]
+    pub fn members(&self) -> &[Member] {
+        &self.members
+    }
+
+    /// Similarity of the most similar pair of questions in the cluster.
+    #[must_use]
+    pub fn similarity(&self) -> f64 {
+        self.similarity
+    }
+}
+
+/// Question belonging to a [`Cluster`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Member {
+    bank: usize,
+    index: usize,
+    number: i32,
+}
+
+impl Member {
+    /// Index of the question bank given to [`DuplicateFinder::find_across`]. Always 0 for
+    /// [`DuplicateFinder::find`].
+    #[must_use]
+    pub fn bank(&self) -> usize {
+        self.bank
+    }
+
+    /// Index of the question in its question bank.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[must_use]
+    pub fn number(&self) -> i32 {
+        self.number
+    }
+}
+
+impl MdQuestions {
+    /// Clusters of similar questions found with the default [`DuplicateFinder`].
+    #[must_use]
+    pub fn near_duplicates(&self) -> Vec<Cluster> {
+        DuplicateFinder::default().find(self)
+    }
+}
+
+// Lowercase words made of letters and digits.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// Both shingle lists are sorted and deduplicated.
+#[allow(clippy::cast_precision_loss)]
+fn jaccard(a: &[u64], b: &[u64]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let (mut i, mut j, mut common) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                common += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    common as f64 / (a.len() + b.len() - common) as f64
+}
+
+// Union-find joining similar questions into clusters and keeping the highest similarity of each.
+struct Groups {
+    parents: Vec<usize>,
+    similarities: Vec<f64>,
+}
+
+impl Groups {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            similarities: vec![0.0; len],
+        }
+    }
+
+    fn root(&mut self, mut idx: usize) -> usize {
+        while self.parents[idx] != idx {
+            self.parents[idx] = self.parents[self.parents[idx]];
+            idx = self.parents[idx];
+        }
+        idx
+    }
+
+    fn join(&mut self, a: usize, b: usize, similarity: f64) {
+        let (a, b) = (self.root(a), self.root(b));
+        let (root, child) = (a.min(b), a.max(b));
+        let highest = similarity
+            .max(self.similarities[root])
+            .max(self.similarities[child]);
+        self.parents[child] = root;
+        self.similarities[root] = highest;
+    }
+
+    // Clusters with at least two members, ordered by their first members.
+    fn clusters(mut self) -> Vec<(Vec<usize>, f64)> {
+        let mut members = vec![Vec::new(); self.parents.len()];
+        for idx in 0..self.parents.len() {
+            let root = self.root(idx);
+            members[root].push(idx);
+        }
+        members
+            .into_iter()
+            .enumerate()
+            .filter(|(_, members)| members.len() > 1)
+            .map(|(root, members)| (members, self.similarities[root]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_finding_near_duplicates() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `AEM`
+            Which OSGi annotation registers the component as a service?
+
+            ## Answers
+            - [ ] @Component
+            - [ ] @Service
+            - [ ] @Reference
+
+            ---
+
+            ## Question 2 `AEM`
+            What does Sling Model adapt from?
+
+            ## Answers
+            - [ ] Resource
+            - [ ] Request
+
+            ---
+
+            ## Question 3 `AEM`
+            Which OSGi annotation registers the component as a service?
+            (Choose one.)
+
+            ## Answers
+            - [ ] @Reference
+            - [ ] @Component
+            - [ ] @Service
+
+            ---
+        "})?;
+
+        let clusters = questions.near_duplicates();
+
+        assert_eq!(clusters.len(), 1);
+        let numbers = clusters[0]
+            .members()
+            .iter()
+            .map(Member::number)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, [1, 3]);
+        assert!(clusters[0].similarity() > 0.8 && clusters[0].similarity() < 1.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_similarity_ignores_case_punctuation_and_order_of_answers() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `AEM`
+            What is OSGi?
+
+            ## Answers
+            - [ ] A bundle
+            - [ ] A service
+
+            ---
+
+            ## Question 2 `AEM`
+            what is osgi
+
+            ## Answers
+            - [ ] a service
+            - [ ] a bundle.
+
+            ---
+
+            ## Question 3 `AEM`
+            What is Sling?
+
+            ## Answers
+            - [ ] A framework
+
+            ---
+        "})?;
+        let finder = DuplicateFinder::default();
+
+        assert!((finder.similarity(&questions[0], &questions[1]) - 1.0).abs() < f64::EPSILON);
+        assert!(finder.similarity(&questions[0], &questions[2]) < 0.5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finding_near_duplicates_across_banks() -> Result<()> {
+        let first = MdQuestions::parse(indoc! {"
+            ## Question 1 `AEM`
+            Which OSGi annotation registers the component as a service?
+
+            ## Answers
+            - [ ] @Component
+            - [ ] @Service
+
+            ---
+
+            ## Question 2 `AEM`
+            What does Sling Model adapt from?
+
+            ## Answers
+            - [ ] Resource
+            - [ ] Request
+
+            ---
+        "})?;
+        let second = MdQuestions::parse(indoc! {"
+            ## Question 7 `AEM`
+            What does the Sling Model adapt from?
+
+            ## Answers
+            - [ ] Request
+            - [ ] Resource
+
+            ---
+        "})?;
+
+        let clusters = DuplicateFinder::default()
+            .threshold(0.4)
+            .find_across(&[&first, &second]);
+
+        assert_eq!(clusters.len(), 1);
+        let [first_member, second_member] = clusters[0].members() else {
+            panic!("expected two members");
+        };
+        assert_eq!(first_member.number(), 2);
+        assert_eq!(second_member.number(), 7);
+        assert_eq!(second_member.bank(), 1);
+        assert_eq!(second_member.index(), 0);
+
+        Ok(())
+    }
+}
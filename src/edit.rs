@@ -0,0 +1,511 @@
+use crate::ignored::IgnoredQuestion;
+use crate::options::RenumberOptions;
+use crate::question::Question;
+use crate::MdQuestions;
+
+use std::fmt::Write;
+
+impl MdQuestions {
+    /// Appends question at the end of the bank.
+    pub fn push(&mut self, question: Question) {
+        self.questions.push(question);
+    }
+
+    /// Inserts question at position `idx`, shifting all questions after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is greater than the number of questions.
+    pub fn insert(&mut self, idx: usize, question: Question) {
+        self.questions.insert(idx, question);
+        for ignored in &mut self.ignored {
+            if ignored.position > idx {
+                ignored.position += 1;
+            }
+        }
+    }
+
+    /// Removes and returns question at position `idx`, shifting all questions after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn remove(&mut self, idx: usize) -> Question {
+        let question = self.questions.remove(idx);
+        for ignored in &mut self.ignored {
+            if ignored.position > idx {
+                ignored.position -= 1;
+            }
+        }
+        question
+    }
+
+    /// Moves question from position `from` to position `to`, shifting the questions in between.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` is out of bounds.
+    pub fn move_question(&mut self, from: usize, to: usize) {
+        assert!(
+            to < self.questions.len(),
+            "failed to move question to idx: {to}"
+        );
+        let question = self.remove(from);
+        self.insert(to, question);
+    }
+
+    /// Orders questions by their numbers. Questions with the same number keep their order.
+    /// Ignored questions are placed after the questions with the same or lower numbers.
+    pub fn sort_by_number(&mut self) {
+        self.questions.sort_by_key(Question::number);
+        for ignored in &mut self.ignored {
+            ignored.position = self
+                .questions
+                .partition_point(|question| question.number() <= ignored.number);
+        }
+        self.ignored.sort_by_key(|ignored| ignored.position);
+    }
+
+    /// Numbers questions sequentially from 1, in the order they appear in the bank. Ignored
+    /// questions are numbered in their places too, their headers are rewritten.
+    pub fn renumber(&mut self) {
+        self.renumber_with(&RenumberOptions::default());
+    }
+
+    /// Numbers questions sequentially like [`MdQuestions::renumber`] using given options. Numbers
+    /// that would overflow `i32` stay at `i32::MAX`.
+    pub fn renumber_with(&mut self, options: &RenumberOptions) {
+        let rewrite_reading = options.rewrites_reading();
+        let mut number = options.first_number();
+        let mut ignored = self.ignored.iter_mut().peekable();
+        for (idx, question) in self.questions.iter_mut().enumerate() {
+            while let Some(ignored) = ignored.next_if(|ignored| ignored.position <= idx) {
+                renumber_ignored(ignored, number, rewrite_reading);
+                number = number.saturating_add(1);
+            }
+            if rewrite_reading {
+                let old = question.number();
+                if let Some(reading) = question.reading_mut() {
+                    *reading = replace_number(reading, old, number);
+                }
+            }
+            question.set_number(number);
+            number = number.saturating_add(1);
+        }
+        for ignored in ignored {
+            renumber_ignored(ignored, number, rewrite_reading);
+            number = number.saturating_add(1);
+        }
+    }
+}
+
+// Ignored questions are kept as raw markdown, so the number is rewritten in the header and in the
+// reading link.
+fn renumber_ignored(ignored: &mut IgnoredQuestion, number: i32, rewrite_reading: bool) {
+    const HEADER: &str = "## Question ";
+    const READING: &str = "## [Reading](";
+    let old = ignored.number;
+    let mut raw = String::with_capacity(ignored.raw.len());
+    for line in ignored.raw.split_inclusive('\n') {
+        if let Some(rest) = line.strip_prefix(HEADER) {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let _ = write!(raw, "{HEADER}{number}{}", &rest[digits..]);
+        } else if let Some(rest) = line.strip_prefix(READING).filter(|_| rewrite_reading) {
+            let end = rest.find(')').unwrap_or(rest.len());
+            let link = replace_number(&rest[..end], old, number);
+            let _ = write!(raw, "{READING}{link}{}", &rest[end..]);
+        } else {
+            raw.push_str(line);
+        }
+    }
+    ignored.raw = raw;
+    ignored.number = number;
+}
+
+// Replaces numbers equal to `old` with `new` in the file name of the link, keeping leading zeros.
+// Digits which are part of a different number or of the directories are left untouched.
+fn replace_number(link: &str, old: i32, new: i32) -> String {
+    let name_start = link.rfind('/').map_or(0, |idx| idx + 1);
+    let mut replaced = link[..name_start].to_string();
+    let mut rest = &link[name_start..];
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        replaced.push_str(&rest[..start]);
+        let digits = &rest[start..];
+        let end = digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len());
+        let number = &digits[..end];
+        if number.parse::<i32>() == Ok(old) {
+            let width = if number.starts_with('0') {
+                number.len()
+            } else {
+                0
+            };
+            let _ = write!(replaced, "{new:0width$}");
+        } else {
+            replaced.push_str(number);
+        }
+        rest = &digits[end..];
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::answer::OpenAnswer;
+    use crate::question::OpenQuestion;
+
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_inserting_and_removing_questions() -> Result<()> {
+        let mut questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 3 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+
+        questions.insert(
+            1,
+            Question::open()
+                .number(2)
+                .text("Text")
+                .answer(OpenAnswer::new("Answer"))
+                .category("Category")
+                .build()?
+                .into(),
+        );
+        questions.push(
+            Question::open()
+                .number(4)
+                .text("Text")
+                .answer(OpenAnswer::new("Answer"))
+                .category("Category")
+                .build()?
+                .into(),
+        );
+        let removed = questions.remove(0);
+
+        assert_eq!(removed.number(), 1);
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_moving_questions() -> Result<()> {
+        let mut questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 2 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 3 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 4 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+
+        questions.move_question(0, 2);
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![2, 3, 1, 4]);
+
+        questions.move_question(3, 0);
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![4, 2, 3, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to move question to idx: 2")]
+    fn test_moving_question_out_of_bounds() {
+        let mut questions =
+            MdQuestions::from(vec![Question::from_open(OpenQuestion::default()); 2]);
+
+        questions.move_question(0, 2);
+    }
+
+    #[test]
+    fn test_editing_keeps_ignored_questions_in_place() -> Result<()> {
+        let mut questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 5 `Category` `Ignore`
+            Unfinished
+
+            ---
+
+            ## Question 2 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+
+        questions.insert(
+            0,
+            Question::open()
+                .number(3)
+                .text("Text")
+                .answer(OpenAnswer::new("Answer"))
+                .category("Category")
+                .build()?
+                .into(),
+        );
+        assert_eq!(questions.ignored()[0].position, 2);
+
+        questions.remove(2);
+        assert_eq!(questions.ignored()[0].position, 2);
+
+        questions.sort_by_number();
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![1, 3]);
+        assert_eq!(questions.ignored()[0].position, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sorting_questions_by_number() -> Result<()> {
+        let mut questions = MdQuestions::parse(indoc! {"
+            ## Question 3 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ## [Reading](a)
+
+            ---
+
+            ## Question 1 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 3 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ## [Reading](b)
+
+            ---
+
+            ## Question 2 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+
+        questions.sort_by_number();
+
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![1, 2, 3, 3]);
+        assert_eq!(questions[2].reading(), Some("a"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_renumbering_questions() -> Result<()> {
+        let mut questions = MdQuestions::parse(indoc! {"
+            ## Question 7 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ## [Reading](reading/question-7.md)
+
+            ---
+
+            ## Question 7 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 2 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+
+        questions.renumber();
+
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(questions[0].reading(), Some("reading/question-7.md"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_renumbering_questions_with_reading_links() -> Result<()> {
+        let mut questions = MdQuestions::parse(indoc! {"
+            ## Question 12 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ## [Reading](reading/question-12.md)
+
+            ---
+
+            ## Question 3 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ## [Reading](reading/question-3.md)
+
+            ---
+        "})?;
+        let options = RenumberOptions::default().start(10).rewrite_reading(true);
+
+        questions.renumber_with(&options);
+
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![10, 11]);
+        assert_eq!(questions[0].reading(), Some("reading/question-10.md"));
+        assert_eq!(questions[1].reading(), Some("reading/question-11.md"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_renumbering_questions_near_the_largest_number() -> Result<()> {
+        let mut questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 2 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+
+        questions.renumber_with(&RenumberOptions::default().start(i32::MAX));
+
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![i32::MAX, i32::MAX]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_renumbering_ignored_questions_in_place() -> Result<()> {
+        let mut questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category` `Ignore`
+            Unfinished
+
+            ## [Reading](reading/question-1.md)
+
+            ---
+
+            ## Question 7 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+
+        questions.renumber_with(&RenumberOptions::default().start(3).rewrite_reading(true));
+
+        let numbers = questions.iter().map(Question::number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![4]);
+        assert_eq!(questions.ignored()[0].number(), 3);
+        assert_eq!(
+            questions.ignored()[0].raw(),
+            "## Question 3 `Category` `Ignore`\nUnfinished\n\n## [Reading](reading/question-3.md)\n\n---"
+        );
+        assert!(questions.to_string().contains("## Question 4 `Category`"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_replacing_numbers() {
+        assert_eq!(replace_number("q-12.md", 12, 3), "q-3.md");
+        assert_eq!(replace_number("q-012.md", 12, 3), "q-003.md");
+        assert_eq!(replace_number("v2/q-12.md", 2, 5), "v2/q-12.md");
+        assert_eq!(replace_number("v2/q-2.md", 2, 5), "v2/q-5.md");
+        assert_eq!(replace_number("q-123.md", 12, 3), "q-123.md");
+        assert_eq!(replace_number("no number", 12, 3), "no number");
+    }
+}
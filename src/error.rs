@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Part of the question convention which could not be parsed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Element {
+    /// ``## Question <number> `<category>` `<marker>` ``
+    Header,
+    /// Question text followed by an empty line.
+    Prolog,
+    /// `## Hints` followed by the numbered list of hints.
+    Hints,
+    /// `## Answers` or `## Answer`
+    AnswersHeader,
+    /// Closed answer: `- [ ] <text>` or `- [X] <text>`
+    Checkbox,
+    /// Text of the open answer.
+    Answer,
+    /// `## Explanation` followed by the explanation text.
+    Explanation,
+    /// Optional reading header followed by questions separator.
+    Epilog,
+    /// Questions separator: `---`
+    HorizontalRule,
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Header => "question header",
+            Self::Prolog => "question text",
+            Self::Hints => "hints",
+            Self::AnswersHeader => "answers header",
+            Self::Checkbox => "answer checkbox",
+            Self::Answer => "open answer",
+            Self::Explanation => "explanation",
+            Self::Epilog => "question epilog",
+            Self::HorizontalRule => "horizontal rule",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Error returned when the markdown doesn't follow the question convention.
+///
+/// Lines and columns are 1-based, columns are counted in characters.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// Input doesn't match the expected grammar element.
+    Syntax {
+        line: usize,
+        column: usize,
+        question: Option<i32>,
+        element: Element,
+    },
+    /// Question number doesn't fit into `i32`.
+    InvalidNumber { line: usize, column: usize },
+}
+
+impl ParseError {
+    #[must_use]
+    pub fn line(&self) -> usize {
+        match self {
+            Self::Syntax { line, .. } | Self::InvalidNumber { line, .. } => *line,
+        }
+    }
+
+    #[must_use]
+    pub fn column(&self) -> usize {
+        match self {
+            Self::Syntax { column, .. } | Self::InvalidNumber { column, .. } => *column,
+        }
+    }
+
+    /// Number of the question being parsed, if its header was readable.
+    #[must_use]
+    pub fn question(&self) -> Option<i32> {
+        match self {
+            Self::Syntax { question, .. } => *question,
+            Self::InvalidNumber { .. } => None,
+        }
+    }
+
+    #[must_use]
+    pub fn element(&self) -> Element {
+        match self {
+            Self::Syntax { element, .. } => *element,
+            Self::InvalidNumber { .. } => Element::Header,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax {
+                line,
+                column,
+                question: Some(question),
+                element,
+            } => write!(
+                f,
+                "invalid {element} of question {question} at {line}:{column}"
+            ),
+            Self::Syntax {
+                line,
+                column,
+                question: None,
+                element,
+            } => write!(f, "invalid {element} at {line}:{column}"),
+            Self::InvalidNumber { line, column } => {
+                write!(f, "question number out of range at {line}:{column}")
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
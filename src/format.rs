@@ -0,0 +1,421 @@
+use crate::error::ParseError;
+use crate::markdown;
+use crate::options::{FormatOptions, ParseOptions, Wrap};
+use crate::parser::{document, Entry};
+use crate::question::Question;
+
+use pulldown_cmark::{Event, Tag};
+
+// Width of `- [ ] `. Continuation lines of answers are indented by it, so it's not available for
+// the answer text.
+const CHECKBOX_WIDTH: usize = 6;
+
+// Width of `> ` starting lines of answer explanations.
+const QUOTE_WIDTH: usize = 2;
+
+/// Rewrites markdown into the canonical form.
+///
+/// Checkboxes are normalised to `[ ]` and `[X]` with `-` bullets, sections are separated by
+/// exactly one blank line, trailing whitespace and `\r\n` line endings are removed. Markers of
+/// question headers are kept. Questions with the `Ignore` marker are kept as they are, apart from
+/// trailing whitespace and line endings.
+///
+/// # Errors
+///
+/// Returns [`ParseError`] when `content` doesn't follow the convention.
+pub fn format(content: &str) -> Result<String, ParseError> {
+    format_with(content, &FormatOptions::default())
+}
+
+/// Rewrites markdown into the canonical form like [`format()`] using given options.
+///
+/// # Errors
+///
+/// Returns [`ParseError`] when `content` doesn't follow the convention.
+pub fn format_with(content: &str, options: &FormatOptions) -> Result<String, ParseError> {
+    let entries = document(content, &ParseOptions::default())?;
+    let formatted = entries
+        .into_iter()
+        .map(|entry| match entry {
+            Entry::Question(question) => wrapped(question, options.wrapping()).to_string(),
+            Entry::Ignored(question) => format_ignored(question.raw()),
+        })
+        .collect::<Vec<_>>();
+    Ok(formatted.join("\n"))
+}
+
+/// Checks whether markdown is already in the canonical form produced by [`format()`].
+///
+/// # Errors
+///
+/// Returns [`ParseError`] when `content` doesn't follow the convention.
+pub fn is_formatted(content: &str) -> Result<bool, ParseError> {
+    is_formatted_with(content, &FormatOptions::default())
+}
+
+/// Checks whether markdown is already in the canonical form produced by [`format_with`].
+///
+/// # Errors
+///
+/// Returns [`ParseError`] when `content` doesn't follow the convention.
+pub fn is_formatted_with(content: &str, options: &FormatOptions) -> Result<bool, ParseError> {
+    Ok(format_with(content, options)? == content)
+}
+
+// Ignored questions don't have to follow the convention, so only whitespace is normalised.
+fn format_ignored(raw: &str) -> String {
+    let mut formatted = raw
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    formatted.push('\n');
+    formatted
+}
+
+fn wrapped(question: Question, wrap: Wrap) -> Question {
+    match question {
+        Question::Closed(mut q) => {
+            q.text = fill_markdown(&q.text, wrap);
+            q.explanation = q.explanation.map(|text| fill_markdown(&text, wrap));
+            q.hints = fill_hints(&q.hints, wrap);
+            for answer in &mut q.answers {
+                answer.text = fill(&answer.text, wrap, CHECKBOX_WIDTH);
+                answer.explanation = answer
+                    .explanation
+                    .take()
+                    .map(|text| fill_paragraphs(&text, wrap, CHECKBOX_WIDTH + QUOTE_WIDTH));
+            }
+            q.into()
+        }
+        Question::Open(mut q) => {
+            q.text = fill_markdown(&q.text, wrap);
+            q.hints = fill_hints(&q.hints, wrap);
+            q.answer.text = fill_markdown(&q.answer.text, wrap);
+            q.into()
+        }
+    }
+}
+
+// Continuation lines of hints are aligned with the text after the `N. ` marker.
+fn fill_hints(hints: &[String], wrap: Wrap) -> Vec<String> {
+    hints
+        .iter()
+        .enumerate()
+        .map(|(idx, hint)| fill(hint, wrap, format!("{}. ", idx + 1).len()))
+        .collect()
+}
+
+// Answer explanations can have empty lines separating paragraphs, which are kept.
+fn fill_paragraphs(text: &str, wrap: Wrap, indent: usize) -> String {
+    text.split("\n\n")
+        .map(|paragraph| fill(paragraph, wrap, indent))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// Only top-level paragraphs are filled. Code blocks, lists, tables and other blocks are kept as
+// they are written.
+fn fill_markdown(text: &str, wrap: Wrap) -> String {
+    if wrap == Wrap::Preserve {
+        return text.to_string();
+    }
+    let mut filled = String::new();
+    let mut last = 0;
+    let mut depth = 0;
+    for (event, range) in markdown::parser(text).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Paragraph) if depth == 0 => {
+                let paragraph = text[range.clone()].trim_end();
+                filled.push_str(&text[last..range.start]);
+                filled.push_str(&fill(paragraph, wrap, 0));
+                last = range.start + paragraph.len();
+                depth += 1;
+            }
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+    }
+    filled.push_str(&text[last..]);
+    filled
+}
+
+fn fill(text: &str, wrap: Wrap, indent: usize) -> String {
+    match wrap {
+        Wrap::Preserve => text.to_string(),
+        Wrap::Never => text.split_whitespace().collect::<Vec<_>>().join(" "),
+        Wrap::At(width) => {
+            let width = width.saturating_sub(indent);
+            let mut lines = Vec::new();
+            let mut line = String::new();
+            for word in text.split_whitespace() {
+                let line_width = line.chars().count();
+                let too_long = line_width + 1 + word.chars().count() > width;
+                if line_width > 0 && too_long && !starts_block(word) {
+                    lines.push(std::mem::take(&mut line));
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
+            lines.push(line);
+            lines.join("\n")
+        }
+    }
+}
+
+// Words which mean something else at the start of a line: headers, quotes, list items and
+// checkboxes, numbered hints, separators, code fences and tables. Lines are never broken before
+// them, even when the line gets too long.
+fn starts_block(word: &str) -> bool {
+    let number_end = word.trim_start_matches(|c: char| c.is_ascii_digit());
+    word.starts_with(['#', '>', '-', '*', '+', '=', '_', '`', '~', '|'])
+        || (number_end.len() < word.len() && number_end.starts_with(['.', ')']))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_formatting_normalises_checkboxes_and_blank_lines() -> Result<()> {
+        let input = indoc! {"
+
+
+            ## Question 1 `Category 1`  
+            Question 1 text
+            
+            ## Answers
+            * [x] Answer 1
+            + [ ] Answer 2
+               wrapped
+
+            ## [Reading](reading/question-1.md)  
+
+            ---
+
+
+
+            ## Question 2 `Category 2`
+            Question 2 text
+
+            ## Answer
+            Answer 2
+
+            ---"};
+
+        assert_eq!(
+            format(input)?,
+            indoc! {"
+                ## Question 1 `Category 1`
+                Question 1 text
+
+                ## Answers
+                - [X] Answer 1
+                - [ ] Answer 2
+                      wrapped
+
+                ## [Reading](reading/question-1.md)
+
+                ---
+
+                ## Question 2 `Category 2`
+                Question 2 text
+
+                ## Answer
+                Answer 2
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatting_keeps_markers() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1` `Draft`
+            Question 1 text
+
+            ## Answer
+            Answer 1
+
+            ---
+
+            ## Question 2 `Category 2` `Ignore`
+            Unfinished question   
+            - [x] Answer
+
+            ---
+        "};
+
+        assert_eq!(
+            format(input)?,
+            indoc! {"
+                ## Question 1 `Category 1` `Draft`
+                Question 1 text
+
+                ## Answer
+                Answer 1
+
+                ---
+
+                ## Question 2 `Category 2` `Ignore`
+                Unfinished question
+                - [x] Answer
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatting_wraps_texts() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Which of these
+            words are long?
+
+            ## Answers
+            - [ ] All of them are long words
+            - [X] None
+
+            ---
+        "};
+        let options = FormatOptions::default().wrap(Wrap::At(16));
+
+        assert_eq!(
+            format_with(input, &options)?,
+            indoc! {"
+                ## Question 1 `Category 1`
+                Which of these
+                words are long?
+
+                ## Answers
+                - [ ] All of
+                      them are
+                      long words
+                - [X] None
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatting_unwraps_texts() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question
+            text
+
+            ## Answer
+            Multi-line
+            answer
+
+            ---
+        "};
+        let options = FormatOptions::default().wrap(Wrap::Never);
+
+        assert_eq!(
+            format_with(input, &options)?,
+            indoc! {"
+                ## Question 1 `Category 1`
+                Question text
+
+                ## Answer
+                Multi-line answer
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_formatting_wraps_only_paragraphs_of_markdown() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            What is
+            printed by:
+            ```rust
+            fn main() { println!(\"{}\", 1 + 1); }
+            ```
+
+            ## Answer
+            - A number
+              of the sum
+
+            ---
+        "};
+        let options = FormatOptions::default().wrap(Wrap::Never);
+
+        assert_eq!(
+            format_with(input, &options)?,
+            indoc! {"
+                ## Question 1 `Category 1`
+                What is printed by:
+                ```rust
+                fn main() { println!(\"{}\", 1 + 1); }
+                ```
+
+                ## Answer
+                - A number
+                  of the sum
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill_never_starts_lines_with_blocks() {
+        assert_eq!(
+            fill("aaaa bbbb > cccc dddd", Wrap::At(16), CHECKBOX_WIDTH),
+            "aaaa bbbb >\ncccc dddd"
+        );
+        assert_eq!(
+            fill("see ## Answers, - [X] and 2. hint", Wrap::At(3), 0),
+            "see ##\nAnswers, -\n[X]\nand 2.\nhint"
+        );
+    }
+
+    #[test]
+    fn test_fill_keeps_too_long_words() {
+        assert_eq!(
+            fill("a verylongword b", Wrap::At(5), 0),
+            "a\nverylongword\nb"
+        );
+    }
+
+    #[test]
+    fn test_checking_formatting() -> Result<()> {
+        let formatted = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answer
+            Answer 1
+
+            ---
+        "};
+
+        assert!(is_formatted(formatted)?);
+        assert!(!is_formatted(&formatted.replace('\n', "\r\n"))?);
+
+        Ok(())
+    }
+}
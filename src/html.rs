@@ -0,0 +1,229 @@
+use crate::answer::{ClosedAnswer, OpenAnswer};
+use crate::markdown::{self, Markdown};
+use crate::question::{ClosedQuestion, OpenQuestion, Question};
+use crate::MdQuestions;
+
+use pulldown_cmark::{CowStr, Event, Tag};
+use std::fmt::Write;
+
+// URL schemes which can run scripts when the link is clicked or the image is loaded.
+const UNSAFE_SCHEMES: [&str; 3] = ["javascript:", "vbscript:", "data:"];
+
+impl MdQuestions {
+    /// Renders all questions as a single HTML page. Answers, hints and explanations are hidden in
+    /// collapsible `<details>` elements.
+    ///
+    /// Raw HTML written in the markdown is escaped and links with `javascript:`, `vbscript:` and
+    /// `data:` URLs are dropped, so the page is safe to show even for untrusted question banks.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(concat!(
+            "<!DOCTYPE html>\n",
+            "<html>\n",
+            "<head>\n",
+            "<meta charset=\"utf-8\">\n",
+            "<title>Questions</title>\n",
+            "</head>\n",
+            "<body>\n",
+        ));
+        for question in &self.questions {
+            write_question(&mut html, question);
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+impl Markdown<'_> {
+    /// Renders the markdown to HTML. Raw HTML is escaped and links with unsafe URLs are dropped.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        pulldown_cmark::html::push_html(&mut html, markdown::parser(self.raw()).map(sanitized));
+        html
+    }
+}
+
+impl Question {
+    /// Question text rendered with [`Markdown::to_html`].
+    #[must_use]
+    pub fn text_html(&self) -> String {
+        self.text_markdown().to_html()
+    }
+}
+
+impl ClosedQuestion {
+    /// Question text rendered with [`Markdown::to_html`].
+    #[must_use]
+    pub fn text_html(&self) -> String {
+        self.text_markdown().to_html()
+    }
+}
+
+impl OpenQuestion {
+    /// Question text rendered with [`Markdown::to_html`].
+    #[must_use]
+    pub fn text_html(&self) -> String {
+        self.text_markdown().to_html()
+    }
+}
+
+impl ClosedAnswer {
+    /// Answer text rendered with [`Markdown::to_html`].
+    #[must_use]
+    pub fn text_html(&self) -> String {
+        self.markdown().to_html()
+    }
+}
+
+impl OpenAnswer {
+    /// Answer rendered with [`Markdown::to_html`].
+    #[must_use]
+    pub fn html(&self) -> String {
+        self.markdown().to_html()
+    }
+}
+
+fn sanitized(event: Event<'_>) -> Event<'_> {
+    match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    }
+}
+
+// Browsers drop tabs and newlines anywhere in a URL and leading control characters and spaces,
+// so the scheme is checked after the same normalisation.
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let lowercase = url
+        .trim_start_matches(|c: char| c.is_ascii_control() || c == ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .to_lowercase();
+    if UNSAFE_SCHEMES
+        .iter()
+        .any(|scheme| lowercase.starts_with(scheme))
+    {
+        CowStr::Borrowed("")
+    } else {
+        url
+    }
+}
+
+// Example of rendered closed question:
+//
+// ```html
+// <section class="question" id="question-3">
+// <h2>Question 3 <small class="category">Category 3</small></h2>
+// <p>Question 3 text</p>
+// <details class="answers">
+// <summary>Answers</summary>
+// <ul>
+// <li class="correct"><p>Answer 1</p>
+// </li>
+// </ul>
+// </details>
+// </section>
+// ```
+fn write_question(html: &mut String, question: &Question) {
+    let number = question.number();
+    let category = escape(question.category());
+    let _ = write!(
+        html,
+        "<section class=\"question\" id=\"question-{number}\">\n\
+         <h2>Question {number} <small class=\"category\">{category}</small></h2>\n"
+    );
+    if !question.tags().is_empty() {
+        html.push_str("<ul class=\"tags\">");
+        for tag in question.tags() {
+            let _ = write!(html, "<li>{}</li>", escape(tag));
+        }
+        html.push_str("</ul>\n");
+    }
+    html.push_str(&question.text_html());
+    if !question.hints().is_empty() {
+        html.push_str("<details class=\"hints\">\n<summary>Hints</summary>\n<ol>\n");
+        for hint in question.hints() {
+            let _ = writeln!(html, "<li>{}</li>", Markdown::new(hint).to_html());
+        }
+        html.push_str("</ol>\n</details>\n");
+    }
+    match question {
+        Question::Closed(q) => write_closed_answers(html, q),
+        Question::Open(q) => {
+            html.push_str("<details class=\"answer\">\n<summary>Answer</summary>\n");
+            html.push_str(&q.answer().html());
+            html.push_str("</details>\n");
+        }
+    }
+    if let Some(reading) = question.reading() {
+        let _ = writeln!(
+            html,
+            "<p class=\"reading\"><a href=\"{}\">Reading</a></p>",
+            escape(&safe_url(reading.into()))
+        );
+    }
+    html.push_str("</section>\n");
+}
+
+fn write_closed_answers(html: &mut String, question: &ClosedQuestion) {
+    html.push_str("<details class=\"answers\">\n<summary>Answers</summary>\n<ul>\n");
+    for answer in question.answers() {
+        let class = if answer.is_correct() {
+            "correct"
+        } else {
+            "incorrect"
+        };
+        let _ = write!(html, "<li class=\"{class}\">{}", answer.text_html());
+        if let Some(explanation) = answer.explanation() {
+            html.push_str("<blockquote>\n");
+            html.push_str(&Markdown::new(explanation).to_html());
+            html.push_str("</blockquote>\n");
+        }
+        html.push_str("</li>\n");
+    }
+    html.push_str("</ul>\n");
+    if let Some(explanation) = question.explanation() {
+        html.push_str("<div class=\"explanation\">\n");
+        html.push_str(&Markdown::new(explanation).to_html());
+        html.push_str("</div>\n");
+    }
+    html.push_str("</details>\n");
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,74 @@
+use crate::error::ParseError;
+use crate::marker::Marker;
+use crate::options::ParseOptions;
+use crate::parser::questions;
+use crate::question::Question;
+use crate::span::{Location, Span};
+
+use getset::{CopyGetters, Getters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Question skipped by the parser because of its markers, e.g. `Ignore`.
+///
+/// Skipped questions don't have to follow the convention, so only the header is parsed and the
+/// rest is kept as raw markdown.
+#[derive(Debug, Getters, CopyGetters, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IgnoredQuestion {
+    #[getset(get_copy = "pub")]
+    pub(crate) number: i32,
+
+    #[getset(get = "pub")]
+    pub(crate) category: String,
+
+    #[getset(get = "pub")]
+    pub(crate) markers: Vec<Marker>,
+
+    /// Markdown of the question from the header up to the questions separator.
+    #[getset(get = "pub")]
+    pub(crate) raw: String,
+
+    // Number of questions before the ignored one, used to render it back in the same place.
+    pub(crate) position: usize,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) location: Location<Span>,
+}
+
+impl IgnoredQuestion {
+    pub(crate) fn new(
+        number: i32,
+        category: String,
+        markers: Vec<Marker>,
+        raw: String,
+        span: Span,
+    ) -> Self {
+        Self {
+            number,
+            category,
+            markers,
+            raw,
+            position: 0,
+            location: Location::new(span),
+        }
+    }
+
+    /// Span of the raw markdown of the question.
+    #[must_use]
+    pub fn span(&self) -> Option<Span> {
+        self.location.get().copied()
+    }
+
+    /// Parses the question as if it wasn't skipped, e.g. to check whether it's finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError`] when the question doesn't follow the convention. Lines and columns
+    /// of the error are counted from the question header.
+    pub fn parse(&self) -> Result<Question, ParseError> {
+        let options = ParseOptions::default().skip_markers([]);
+        let mut questions = questions(&format!("{}\n", self.raw), &options)?;
+        Ok(questions.remove(0))
+    }
+}
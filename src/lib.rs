@@ -1,24 +1,103 @@
 #![allow(clippy::module_name_repetitions)]
 
 use getset::Getters;
-use parser::questions;
+use parser::{questions, questions_recovering};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::ops::Index;
+use std::str::FromStr;
 
 pub use crate::answer::{ClosedAnswer, OpenAnswer};
+pub use crate::diagnostic::{Diagnostic, Severity};
+pub use crate::error::{Element, ParseError};
+pub use crate::format::{format, format_with, is_formatted, is_formatted_with};
+pub use crate::ignored::IgnoredQuestion;
+pub use crate::markdown::{CodeBlock, Image, Markdown};
+pub use crate::marker::Marker;
+pub use crate::options::{FormatOptions, LineBreaks, ParseOptions, RenumberOptions, Wrap};
+pub use crate::query::Query;
 pub use crate::question::{ClosedQuestion, OpenQuestion, Question};
+pub use crate::sample::ShuffledAnswers;
+pub use crate::span::{Position, QuestionSpans, Span};
 
 mod answer;
+mod diagnostic;
+pub mod duplicates;
+mod edit;
+mod error;
+mod format;
+#[cfg(feature = "html")]
+mod html;
+mod ignored;
+pub mod lint;
+mod markdown;
+mod marker;
+mod options;
 mod parser;
+mod query;
 mod question;
+pub mod quiz;
+mod sample;
+pub mod search;
+mod span;
+mod writer;
 
 #[derive(Debug, Getters, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MdQuestions {
     questions: Vec<Question>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    ignored: Vec<IgnoredQuestion>,
 }
 
 impl MdQuestions {
     fn new(questions: Vec<Question>) -> Self {
-        Self { questions }
+        Self {
+            questions,
+            ignored: Vec::new(),
+        }
+    }
+
+    fn with_ignored(mut self, ignored: Vec<IgnoredQuestion>) -> Self {
+        self.ignored = ignored;
+        self
+    }
+
+    /// Parses questions written using the convention described in README.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError`] pointing to the first place where `content` doesn't follow the
+    /// convention.
+    pub fn parse(content: &str) -> Result<Self, ParseError> {
+        Self::parse_with(content, &ParseOptions::default())
+    }
+
+    /// Parses questions like [`MdQuestions::parse`] using given options.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError`] pointing to the first place where `content` doesn't follow the
+    /// convention.
+    pub fn parse_with(content: &str, options: &ParseOptions) -> Result<Self, ParseError> {
+        questions(content, options)
+    }
+
+    /// Parses questions like [`MdQuestions::parse`], but doesn't stop on malformed questions.
+    ///
+    /// Each malformed question is skipped up to the next question header (`## Question`) and
+    /// reported as a [`Diagnostic`]. Parsing continues with the next question.
+    #[must_use]
+    pub fn parse_recovering(content: &str) -> (Self, Vec<Diagnostic>) {
+        Self::parse_recovering_with(content, &ParseOptions::default())
+    }
+
+    /// Parses questions like [`MdQuestions::parse_recovering`] using given options.
+    #[must_use]
+    pub fn parse_recovering_with(content: &str, options: &ParseOptions) -> (Self, Vec<Diagnostic>) {
+        questions_recovering(content, options)
     }
 
     #[must_use]
@@ -30,12 +109,112 @@ impl MdQuestions {
     pub fn is_empty(&self) -> bool {
         self.questions.is_empty()
     }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Question> {
+        self.questions.iter()
+    }
+
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<&Question> {
+        self.questions.get(idx)
+    }
+
+    /// First question with given number. Question numbers don't have to be unique nor sorted.
+    #[must_use]
+    pub fn by_number(&self, number: i32) -> Option<&Question> {
+        self.questions.iter().find(|q| q.number() == number)
+    }
+
+    pub fn closed(&self) -> impl Iterator<Item = &ClosedQuestion> {
+        self.questions.iter().filter_map(Question::as_closed)
+    }
+
+    pub fn open(&self) -> impl Iterator<Item = &OpenQuestion> {
+        self.questions.iter().filter_map(Question::as_open)
+    }
+
+    pub fn by_category<'a>(&'a self, category: &'a str) -> impl Iterator<Item = &'a Question> + 'a {
+        self.questions
+            .iter()
+            .filter(move |q| q.category() == category)
+    }
+
+    /// Categories of the questions with numbers of questions in each of them.
+    #[must_use]
+    pub fn categories(&self) -> BTreeMap<&str, usize> {
+        let mut categories = BTreeMap::new();
+        for question in &self.questions {
+            *categories.entry(question.category()).or_default() += 1;
+        }
+        categories
+    }
+
+    /// Questions having at least one of given tags.
+    pub fn with_any_tag<'a, S: AsRef<str>>(
+        &'a self,
+        tags: &'a [S],
+    ) -> impl Iterator<Item = &'a Question> + 'a {
+        self.questions.iter().filter(|q| q.has_any_tag(tags))
+    }
+
+    /// Questions having all given tags.
+    pub fn with_all_tags<'a, S: AsRef<str>>(
+        &'a self,
+        tags: &'a [S],
+    ) -> impl Iterator<Item = &'a Question> + 'a {
+        self.questions.iter().filter(|q| q.has_all_tags(tags))
+    }
+
+    /// Questions skipped because of their markers. See [`ParseOptions::skip_markers`].
+    #[must_use]
+    pub fn ignored(&self) -> &[IgnoredQuestion] {
+        &self.ignored
+    }
+}
+
+impl From<Vec<Question>> for MdQuestions {
+    fn from(questions: Vec<Question>) -> Self {
+        Self::new(questions)
+    }
+}
+
+impl FromIterator<Question> for MdQuestions {
+    fn from_iter<I: IntoIterator<Item = Question>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for MdQuestions {
+    type Item = Question;
+    type IntoIter = std::vec::IntoIter<Question>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.questions.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a MdQuestions {
+    type Item = &'a Question;
+    type IntoIter = std::slice::Iter<'a, Question>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.questions.iter()
+    }
 }
 
-impl From<&str> for MdQuestions {
-    fn from(content: &str) -> Self {
-        let (_, questions) = questions(content).expect("failed to parse questions");
-        questions
+impl TryFrom<&str> for MdQuestions {
+    type Error = ParseError;
+
+    fn try_from(content: &str) -> Result<Self, Self::Error> {
+        Self::parse(content)
+    }
+}
+
+impl FromStr for MdQuestions {
+    type Err = ParseError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        Self::parse(content)
     }
 }
 
@@ -0,0 +1,807 @@
+//! Checks of the question bank quality.
+//!
+//! [`Linter`] runs [`Rule`]s over parsed questions and collects the problems in a [`LintReport`].
+//! Built-in rules are enabled by default, their severities can be changed and custom rules can be
+//! added:
+//!
+//! ```
+//! use md_questions::lint::Linter;
+//! use md_questions::{MdQuestions, Severity};
+//!
+//! let questions = MdQuestions::parse("")?;
+//! let linter = Linter::default()
+//!     .severity("non-sequential-number", Severity::Error)
+//!     .disable("empty-category");
+//! let report = linter.lint(&questions);
+//! assert!(!report.has_errors());
+//! # Ok::<(), md_questions::ParseError>(())
+//! ```
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::duplicates::DuplicateFinder;
+use crate::question::Question;
+use crate::span::{QuestionSpans, Span};
+use crate::MdQuestions;
+
+use getset::Getters;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+
+/// Check of the question bank.
+pub trait Rule {
+    /// Unique name of the rule used in the configuration and in reports, e.g. `duplicate-number`.
+    fn name(&self) -> &'static str;
+
+    /// Severity of the problems found by the rule, unless configured otherwise.
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Returns problems found in `questions`.
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation>;
+}
+
+/// Problem found by a [`Rule`]. Span is available only for parsed questions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Violation {
+    span: Option<Span>,
+    message: String,
+}
+
+impl Violation {
+    pub fn new<S: Into<String>>(span: Option<Span>, message: S) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// Runs rules over questions.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    severities: HashMap<String, Option<Severity>>,
+}
+
+impl Default for Linter {
+    /// Linter with all built-in rules.
+    fn default() -> Self {
+        Self::empty()
+            .rule(NoCorrectAnswer)
+            .rule(DuplicateAnswer)
+            .rule(DuplicateNumber)
+            .rule(NonSequentialNumber)
+            .rule(EmptyCategory)
+            .rule(ChoiceCountMismatch)
+            .rule(ParseableIgnoredQuestion)
+    }
+}
+
+impl Linter {
+    /// Linter without any rules.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            severities: HashMap::new(),
+        }
+    }
+
+    /// Adds a rule.
+    #[must_use]
+    pub fn rule<R: Rule + 'static>(mut self, rule: R) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Overrides the severity of the rule with given name.
+    #[must_use]
+    pub fn severity<S: Into<String>>(mut self, rule: S, severity: Severity) -> Self {
+        self.severities.insert(rule.into(), Some(severity));
+        self
+    }
+
+    /// Turns off the rule with given name.
+    #[must_use]
+    pub fn disable<S: Into<String>>(mut self, rule: S) -> Self {
+        self.severities.insert(rule.into(), None);
+        self
+    }
+
+    /// Runs all enabled rules. Problems are ordered by their position in the markdown, problems
+    /// without position come first.
+    #[must_use]
+    pub fn lint(&self, questions: &MdQuestions) -> LintReport {
+        let mut diagnostics = Vec::new();
+        for rule in &self.rules {
+            let severity = match self.severities.get(rule.name()) {
+                Some(Some(severity)) => *severity,
+                Some(None) => continue,
+                None => rule.default_severity(),
+            };
+            let violations = rule.check(questions).into_iter().map(|violation| {
+                Diagnostic::new(severity, violation.span, violation.message)
+                    .reported_by(rule.name())
+            });
+            diagnostics.extend(violations);
+        }
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span().map(|span| span.start().offset()));
+        LintReport { diagnostics }
+    }
+}
+
+impl MdQuestions {
+    /// Checks questions with all built-in rules. See [`Linter`].
+    #[must_use]
+    pub fn lint(&self) -> LintReport {
+        Linter::default().lint(self)
+    }
+}
+
+/// Problems found by the [`Linter`].
+///
+/// With the `serde` feature enabled, the report can be serialized, e.g. to JSON for CI tools.
+#[derive(Debug, Default, Getters, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LintReport {
+    #[getset(get = "pub")]
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl LintReport {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    /// Number of problems with given severity.
+    #[must_use]
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity() == severity)
+            .count()
+    }
+}
+
+impl Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+fn header_span(question: &Question) -> Option<Span> {
+    question.spans().map(QuestionSpans::header)
+}
+
+/// Closed question without any correct answer.
+pub struct NoCorrectAnswer;
+
+impl Rule for NoCorrectAnswer {
+    fn name(&self) -> &'static str {
+        "no-correct-answer"
+    }
+
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+        questions
+            .questions
+            .iter()
+            .filter_map(Question::as_closed)
+            .filter(|q| q.answers().iter().all(|answer| !answer.is_correct()))
+            .map(|q| {
+                let span = q.spans().map(QuestionSpans::answers);
+                Violation::new(
+                    span,
+                    format!("question {} has no correct answer", q.number()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Closed question with the same answer listed more than once.
+pub struct DuplicateAnswer;
+
+impl Rule for DuplicateAnswer {
+    fn name(&self) -> &'static str {
+        "duplicate-answer"
+    }
+
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for q in questions.questions.iter().filter_map(Question::as_closed) {
+            let mut seen = HashSet::new();
+            for answer in q.answers() {
+                let text = normalized(answer.text());
+                if !seen.insert(text) {
+                    let message = format!(
+                        "question {} has duplicate answer '{}'",
+                        q.number(),
+                        answer.text()
+                    );
+                    violations.push(Violation::new(answer.span(), message));
+                }
+            }
+        }
+        violations
+    }
+}
+
+fn normalized(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Question number used by more than one question.
+pub struct DuplicateNumber;
+
+impl Rule for DuplicateNumber {
+    fn name(&self) -> &'static str {
+        "duplicate-number"
+    }
+
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+        let mut seen = HashSet::new();
+        questions
+            .questions
+            .iter()
+            .filter(|q| !seen.insert(q.number()))
+            .map(|q| {
+                let message = format!("question number {} is already used", q.number());
+                Violation::new(header_span(q), message)
+            })
+            .collect()
+    }
+}
+
+/// Question number which doesn't follow the number of the previous question. Duplicates are
+/// reported by [`DuplicateNumber`].
+pub struct NonSequentialNumber;
+
+impl Rule for NonSequentialNumber {
+    fn name(&self) -> &'static str {
+        "non-sequential-number"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut seen = HashSet::new();
+        let mut previous: Option<i32> = None;
+        for q in &questions.questions {
+            let number = q.number();
+            let expected = previous.and_then(|previous| previous.checked_add(1));
+            if seen.insert(number) && expected.is_some_and(|expected| expected != number) {
+                let expected = expected.unwrap_or_default();
+                let message = format!("expected question number {expected}, found {number}");
+                violations.push(Violation::new(header_span(q), message));
+            }
+            previous = Some(number);
+        }
+        violations
+    }
+}
+
+/// Question with an empty category.
+pub struct EmptyCategory;
+
+impl Rule for EmptyCategory {
+    fn name(&self) -> &'static str {
+        "empty-category"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+        questions
+            .questions
+            .iter()
+            .filter(|q| q.category().trim().is_empty())
+            .map(|q| {
+                let message = format!("question {} has empty category", q.number());
+                Violation::new(header_span(q), message)
+            })
+            .collect()
+    }
+}
+
+/// Closed question which asks to choose a number of answers, e.g. `(Choose two.)`, but has a
+/// different number of correct answers.
+pub struct ChoiceCountMismatch;
+
+impl Rule for ChoiceCountMismatch {
+    fn name(&self) -> &'static str {
+        "choice-count-mismatch"
+    }
+
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for q in questions.questions.iter().filter_map(Question::as_closed) {
+            let Some(expected) = q.expected_choices() else {
+                continue;
+            };
+            let correct = q.correct_count();
+            if correct != expected {
+                let answers = if expected == 1 { "answer" } else { "answers" };
+                let message = format!(
+                    "question {} asks to choose {expected} {answers}, but has {correct} correct",
+                    q.number()
+                );
+                let span = q.spans().map(QuestionSpans::text);
+                violations.push(Violation::new(span, message));
+            }
+        }
+        violations
+    }
+}
+
+/// Ignored question which follows the convention, so it can be un-ignored.
+pub struct ParseableIgnoredQuestion;
+
+impl Rule for ParseableIgnoredQuestion {
+    fn name(&self) -> &'static str {
+        "parseable-ignored-question"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+        questions
+            .ignored()
+            .iter()
+            .filter(|ignored| ignored.parse().is_ok())
+            .map(|ignored| {
+                let message = format!("ignored question {} can be parsed", ignored.number());
+                Violation::new(ignored.span(), message)
+            })
+            .collect()
+    }
+}
+
+/// Questions similar to an earlier question. See [`DuplicateFinder`].
+///
+/// Comparing all questions with each other is slow for big question banks, so the rule is not
+/// enabled by default. Add it with [`Linter::rule`].
+#[derive(Debug, Default, Clone)]
+pub struct NearDuplicateQuestion {
+    finder: DuplicateFinder,
+}
+
+impl NearDuplicateQuestion {
+    #[must_use]
+    pub fn new(finder: DuplicateFinder) -> Self {
+        Self { finder }
+    }
+}
+
+impl Rule for NearDuplicateQuestion {
+    fn name(&self) -> &'static str {
+        "near-duplicate-question"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for cluster in self.finder.find(questions) {
+            let members = cluster
+                .members()
+                .iter()
+                .map(|member| &questions[member.index()])
+                .collect::<Vec<_>>();
+            // Clusters are joined transitively, so each question is reported with its best match,
+            // which is at least as similar as the threshold.
+            for (idx, question) in members.iter().enumerate().skip(1) {
+                let best = members
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other_idx, _)| other_idx != idx)
+                    .map(|(_, other)| (other, self.finder.similarity(question, other)))
+                    .reduce(|best, next| if next.1 > best.1 { next } else { best });
+                let Some((other, similarity)) = best else {
+                    continue;
+                };
+                let message = format!(
+                    "question {} is {:.0}% similar to question {}",
+                    question.number(),
+                    similarity * 100.0,
+                    other.number()
+                );
+                violations.push(Violation::new(header_span(question), message));
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_linting_parsed_questions() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Question 1 text
+
+            ## Answers
+            - [ ] Answer 1
+            - [ ] Answer 2
+
+            ---
+
+            ## Question 3 ` `
+            Which two answers are correct?
+
+            ## Answers
+            - [X] Answer 1
+            - [ ] answer  1
+
+            ---
+        "})?;
+
+        let report = questions.lint();
+
+        let messages = report
+            .diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "5:1: error[no-correct-answer]: question 1 has no correct answer",
+                "10:1: warning[non-sequential-number]: expected question number 2, found 3",
+                "10:1: warning[empty-category]: question 3 has empty category",
+                "11:1: error[choice-count-mismatch]: question 3 asks to choose 2 answers, but \
+                 has 1 correct",
+                "15:1: error[duplicate-answer]: question 3 has duplicate answer 'answer  1'",
+            ]
+        );
+        assert!(report.has_errors());
+        assert_eq!(report.count(Severity::Warning), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_linting_choice_counts() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Which option is correct? (Choose one.)
+
+            ## Answers
+            - [X] Answer 1
+            - [X] Answer 2
+
+            ---
+
+            ## Question 2 `Category`
+            Select one or more answers.
+
+            ## Answers
+            - [X] Answer 1
+            - [X] Answer 2
+
+            ---
+        "})?;
+
+        let report = Linter::empty().rule(ChoiceCountMismatch).lint(&questions);
+
+        let messages = report
+            .diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "2:1: error[choice-count-mismatch]: question 1 asks to choose 1 answer, but has 2 \
+                 correct"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_linting_ignored_questions() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category` `Ignore`
+            Question 1 text
+
+            ## Answers
+            - [X] Answer 1
+
+            ---
+
+            ## Question 2 `Category` `Ignore`
+            Question 2 text
+
+            ---
+        "})?;
+
+        let report = questions.lint();
+
+        let messages = report
+            .diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec!["1:1: warning[parseable-ignored-question]: ignored question 1 can be parsed"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_linting_duplicate_numbers() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Text
+
+            ## Answers
+            - [X] Answer
+
+            ---
+
+            ## Question 2 `Category`
+            Text
+
+            ## Answers
+            - [X] Answer
+
+            ---
+
+            ## Question 2 `Category`
+            Text
+
+            ## Answers
+            - [X] Answer
+
+            ---
+
+            ## Question 3 `Category`
+            Text
+
+            ## Answers
+            - [X] Answer
+
+            ---
+
+            ## Question 1 `Category`
+            Text
+
+            ## Answers
+            - [X] Answer
+
+            ---
+        "})?;
+
+        let report = questions.lint();
+
+        let messages = report
+            .diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "17:1: error[duplicate-number]: question number 2 is already used",
+                "33:1: error[duplicate-number]: question number 1 is already used",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_linting_near_duplicates() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Is OSGi a dynamic module system for Java?
+
+            ## Answers
+            - [X] Yes
+            - [ ] No
+
+            ---
+
+            ## Question 2 `Category`
+            Is Sling a web framework?
+
+            ## Answers
+            - [X] Yes
+            - [ ] No
+
+            ---
+
+            ## Question 3 `Category`
+            Is OSGi a dynamic module system for Java
+
+            ## Answers
+            - [X] Yes
+            - [ ] No
+
+            ---
+        "})?;
+
+        let report = Linter::empty()
+            .rule(NearDuplicateQuestion::default())
+            .lint(&questions);
+
+        let messages = report
+            .diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "19:1: warning[near-duplicate-question]: question 3 is 100% similar to question 1"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_linting_near_duplicates_reports_best_matches() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            one two three four five six
+
+            ## Answers
+            - [X] Yes
+            - [ ] No
+
+            ---
+
+            ## Question 2 `Category`
+            one two three four five six seven eight nine
+
+            ## Answers
+            - [X] Yes
+            - [ ] No
+
+            ---
+
+            ## Question 3 `Category`
+            four five six seven eight nine
+
+            ## Answers
+            - [X] Yes
+            - [ ] No
+
+            ---
+        "})?;
+
+        let report = Linter::empty()
+            .rule(NearDuplicateQuestion::new(
+                DuplicateFinder::default().threshold(0.6),
+            ))
+            .lint(&questions);
+
+        let messages = report
+            .diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "10:1: warning[near-duplicate-question]: question 2 is 67% similar to question 1",
+                "19:1: warning[near-duplicate-question]: question 3 is 67% similar to question 2",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_configuring_severities() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `Category`
+            Text
+
+            ## Answers
+            - [ ] Answer
+
+            ---
+
+            ## Question 3 `Category`
+            Text
+
+            ## Answers
+            - [X] Answer
+
+            ---
+        "})?;
+        let linter = Linter::default()
+            .disable("no-correct-answer")
+            .severity("non-sequential-number", Severity::Error);
+
+        let report = linter.lint(&questions);
+
+        let messages = report
+            .diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec!["9:1: error[non-sequential-number]: expected question number 2, found 3"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_linting_with_custom_rule() -> Result<()> {
+        struct ShortText;
+
+        impl Rule for ShortText {
+            fn name(&self) -> &'static str {
+                "short-text"
+            }
+
+            fn default_severity(&self) -> Severity {
+                Severity::Warning
+            }
+
+            fn check(&self, questions: &MdQuestions) -> Vec<Violation> {
+                questions
+                    .questions
+                    .iter()
+                    .filter(|q| q.text().len() < 10)
+                    .map(|q| Violation::new(None, format!("question {} is short", q.number())))
+                    .collect()
+            }
+        }
+        let questions = MdQuestions::from(vec![Question::closed()
+            .number(1)
+            .text("Text")
+            .answers(vec![])
+            .category("Category")
+            .build()?
+            .into()]);
+
+        let report = Linter::empty().rule(ShortText).lint(&questions);
+
+        let messages = report
+            .diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["warning[short-text]: question 1 is short"]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+/// Structured view of the markdown written in question texts and answers.
+///
+/// Question texts, open answers and question explanations can contain any markdown blocks:
+/// paragraphs, lists, fenced code blocks, images and tables. Closed answers are single paragraphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Markdown<'a> {
+    raw: &'a str,
+}
+
+impl<'a> Markdown<'a> {
+    #[must_use]
+    pub fn new(raw: &'a str) -> Self {
+        Self { raw }
+    }
+
+    /// Markdown as written in the file.
+    #[must_use]
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
+    /// Fenced and indented code blocks in the order they appear.
+    #[must_use]
+    pub fn code_blocks(&self) -> Vec<CodeBlock> {
+        let mut blocks = Vec::new();
+        let mut current: Option<CodeBlock> = None;
+        for event in parser(self.raw) {
+            match (event, &mut current) {
+                (Event::Start(Tag::CodeBlock(kind)), _) => {
+                    current = Some(CodeBlock {
+                        language: language(&kind),
+                        code: String::new(),
+                    });
+                }
+                (Event::Text(text), Some(block)) => block.code.push_str(&text),
+                (Event::End(TagEnd::CodeBlock), _) => blocks.extend(current.take()),
+                _ => {}
+            }
+        }
+        blocks
+    }
+
+    /// Images in the order they appear, including images nested in links and lists.
+    #[must_use]
+    pub fn images(&self) -> Vec<Image> {
+        let mut images = Vec::new();
+        let mut current: Option<Image> = None;
+        for event in parser(self.raw) {
+            match (event, &mut current) {
+                (Event::Start(Tag::Image { dest_url, .. }), _) => {
+                    current = Some(Image {
+                        alt: String::new(),
+                        src: dest_url.to_string(),
+                    });
+                }
+                (Event::Text(text) | Event::Code(text), Some(image)) => image.alt.push_str(&text),
+                (Event::End(TagEnd::Image), _) => images.extend(current.take()),
+                _ => {}
+            }
+        }
+        images
+    }
+
+    /// Texts of the items of top-level lists without the list markers, in the order they appear.
+    /// Lines of multi-line items are joined with `\n`.
+    #[must_use]
+    pub fn list_items(&self) -> Vec<String> {
+        let mut items = Vec::new();
+        let mut depth = 0;
+        for (event, range) in parser(self.raw).into_offset_iter() {
+            match event {
+                Event::Start(Tag::Item) if depth == 1 => {
+                    items.push(item_text(&self.raw[range]));
+                    depth += 1;
+                }
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                _ => {}
+            }
+        }
+        items
+    }
+
+    /// Contents of inline code spans (`` `code` ``) in the order they appear.
+    #[must_use]
+    pub fn inline_code(&self) -> Vec<String> {
+        parser(self.raw)
+            .filter_map(|event| match event {
+                Event::Code(code) => Some(code.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Code block found in markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    language: Option<String>,
+    code: String,
+}
+
+impl CodeBlock {
+    /// Language from the info string of fenced code block, e.g. `rust` for ` ```rust `.
+    #[must_use]
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    #[must_use]
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+/// Image found in markdown: `![alt](src)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    alt: String,
+    src: String,
+}
+
+impl Image {
+    #[must_use]
+    pub fn alt(&self) -> &str {
+        &self.alt
+    }
+
+    #[must_use]
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+}
+
+// Tables and strikethrough are common in question banks, so they are recognized as well.
+pub(crate) fn parser(text: &str) -> Parser<'_> {
+    Parser::new_ext(text, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH)
+}
+
+// Item source without the `1.`, `1)`, `-`, `*` or `+` marker and indentation.
+fn item_text(item: &str) -> String {
+    let item = item.trim_start();
+    let text = match item.trim_start_matches(|c: char| c.is_ascii_digit()) {
+        rest if rest.len() < item.len() => rest.trim_start_matches(['.', ')']),
+        _ => item.trim_start_matches(['-', '*', '+']),
+    };
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn language(kind: &CodeBlockKind<'_>) -> Option<String> {
+    match kind {
+        CodeBlockKind::Fenced(info) => info.split_whitespace().next().map(str::to_string),
+        CodeBlockKind::Indented => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use indoc::indoc;
+
+    const TEXT: &str = indoc! {"
+        What does `cargo check` print for:
+
+        ```rust,ignore
+        fn main() {
+
+            let x: i32 = \"1\";
+        }
+        ```
+
+        ![Compiler output](img/output.png)
+
+            indented code
+    "};
+
+    #[test]
+    fn test_code_blocks_with_language() {
+        let blocks = Markdown::new(TEXT).code_blocks();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].language(), Some("rust,ignore"));
+        assert_eq!(
+            blocks[0].code(),
+            "fn main() {\n\n    let x: i32 = \"1\";\n}\n"
+        );
+        assert_eq!(blocks[1].language(), None);
+        assert_eq!(blocks[1].code(), "indented code\n");
+    }
+
+    #[test]
+    fn test_images_and_inline_code() {
+        let markdown = Markdown::new(TEXT);
+
+        let images = markdown.images();
+
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].alt(), "Compiler output");
+        assert_eq!(images[0].src(), "img/output.png");
+        assert_eq!(markdown.inline_code(), ["cargo check"]);
+    }
+
+    #[test]
+    fn test_list_items() {
+        let markdown = Markdown::new(indoc! {"
+            Graphs:
+
+            1. Undirected
+            2) Directed
+               (Digraph)
+               - Nested
+
+            * Tree
+        "});
+
+        assert_eq!(
+            markdown.list_items(),
+            ["Undirected", "Directed\n(Digraph)\n- Nested", "Tree"]
+        );
+    }
+}
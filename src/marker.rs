@@ -0,0 +1,82 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Marker of the question written after the category, e.g. ``## Question 1 `Category` `Draft` ``.
+///
+/// Names of the known markers are matched ignoring the case. Any other name is kept as
+/// [`Marker::Custom`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "String", into = "String")
+)]
+pub enum Marker {
+    /// Question is skipped by default. See [`crate::ParseOptions::skip_markers`].
+    Ignore,
+    Draft,
+    Review,
+    Hard,
+    Deprecated,
+    Custom(String),
+}
+
+impl From<&str> for Marker {
+    fn from(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "ignore" => Self::Ignore,
+            "draft" => Self::Draft,
+            "review" => Self::Review,
+            "hard" => Self::Hard,
+            "deprecated" => Self::Deprecated,
+            _ => Self::Custom(name.to_string()),
+        }
+    }
+}
+
+impl From<String> for Marker {
+    fn from(name: String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+
+impl From<Marker> for String {
+    fn from(marker: Marker) -> Self {
+        marker.to_string()
+    }
+}
+
+impl Display for Marker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ignore => write!(f, "Ignore"),
+            Self::Draft => write!(f, "Draft"),
+            Self::Review => write!(f, "Review"),
+            Self::Hard => write!(f, "Hard"),
+            Self::Deprecated => write!(f, "Deprecated"),
+            Self::Custom(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reading_markers() {
+        assert_eq!(Marker::from("IGNORE"), Marker::Ignore);
+        assert_eq!(Marker::from("draft"), Marker::Draft);
+        assert_eq!(
+            Marker::from("Needs Image"),
+            Marker::Custom("Needs Image".into())
+        );
+    }
+
+    #[test]
+    fn test_displaying_markers() {
+        assert_eq!(Marker::Deprecated.to_string(), "Deprecated");
+        assert_eq!(Marker::Custom("exam-2023".into()).to_string(), "exam-2023");
+    }
+}
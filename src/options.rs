@@ -0,0 +1,130 @@
+use crate::marker::Marker;
+
+/// How line breaks of multi-line answers are kept in the parsed text.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum LineBreaks {
+    /// Lines are joined with `\n`, indentation of continuation lines is removed.
+    #[default]
+    Preserve,
+    /// Lines are joined with a single space.
+    Join,
+}
+
+/// Options changing how the markdown is parsed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseOptions {
+    line_breaks: LineBreaks,
+    skip_markers: Vec<Marker>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            line_breaks: LineBreaks::default(),
+            skip_markers: vec![Marker::Ignore],
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Sets how line breaks of multi-line closed answers are kept. See [`LineBreaks`].
+    #[must_use]
+    pub fn line_breaks(mut self, line_breaks: LineBreaks) -> Self {
+        self.line_breaks = line_breaks;
+        self
+    }
+
+    /// Sets markers causing questions to be skipped. Questions with other markers are parsed and
+    /// keep their markers. Only [`Marker::Ignore`] is skipped by default.
+    ///
+    /// Skipped questions don't have to follow the convention.
+    #[must_use]
+    pub fn skip_markers<I: IntoIterator<Item = Marker>>(mut self, markers: I) -> Self {
+        self.skip_markers = markers.into_iter().collect();
+        self
+    }
+
+    pub(crate) fn skips(&self, marker: &Marker) -> bool {
+        self.skip_markers.contains(marker)
+    }
+
+    pub(crate) fn line_separator(&self) -> &'static str {
+        match self.line_breaks {
+            LineBreaks::Preserve => "\n",
+            LineBreaks::Join => " ",
+        }
+    }
+}
+
+/// How texts of questions and answers are wrapped by the formatter.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum Wrap {
+    /// Line breaks are kept as they are.
+    #[default]
+    Preserve,
+    /// Texts are reflowed to fit in the given number of columns. Words longer than the limit are
+    /// kept on their own lines.
+    At(usize),
+    /// Texts are joined into single lines.
+    Never,
+}
+
+/// Options changing how the markdown is formatted.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct FormatOptions {
+    wrap: Wrap,
+}
+
+impl FormatOptions {
+    /// Sets how texts of questions and answers are wrapped. See [`Wrap`].
+    #[must_use]
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub(crate) fn wrapping(&self) -> Wrap {
+        self.wrap
+    }
+}
+
+/// Options changing how questions are renumbered.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RenumberOptions {
+    start: i32,
+    rewrite_reading: bool,
+}
+
+impl Default for RenumberOptions {
+    fn default() -> Self {
+        Self {
+            start: 1,
+            rewrite_reading: false,
+        }
+    }
+}
+
+impl RenumberOptions {
+    /// Sets the number of the first question. Defaults to 1.
+    #[must_use]
+    pub fn start(mut self, start: i32) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Sets whether the old number embedded in the file name of reading links (e.g.
+    /// `reading/question-12.md`) is replaced with the new one. Disabled by default.
+    #[must_use]
+    pub fn rewrite_reading(mut self, rewrite_reading: bool) -> Self {
+        self.rewrite_reading = rewrite_reading;
+        self
+    }
+
+    pub(crate) fn first_number(&self) -> i32 {
+        self.start
+    }
+
+    pub(crate) fn rewrites_reading(&self) -> bool {
+        self.rewrite_reading
+    }
+}
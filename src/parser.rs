@@ -1,29 +1,230 @@
 use crate::answer::{ClosedAnswer, OpenAnswer};
+use crate::diagnostic::Diagnostic;
+use crate::error::{Element, ParseError};
+use crate::ignored::IgnoredQuestion;
+use crate::marker::Marker;
+use crate::options::ParseOptions;
 use crate::question::{ClosedQuestionBuilder, OpenQuestion, Question};
+use crate::span::{Location, Position, QuestionSpans, Span};
 use crate::MdQuestions;
 
 use log::{debug, warn};
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_until};
-use nom::character::complete::{char, digit1, newline};
-use nom::combinator::{map_res, opt};
-use nom::multi::many1;
-use nom::sequence::tuple;
-use nom::IResult;
+use nom::bytes::complete::{tag, take_till, take_until};
+use nom::character::complete::{char, digit1, line_ending, newline, one_of, space0, space1};
+use nom::combinator::{cut, eof, map, map_res, not, opt, peek, recognize, rest, value, verify};
+use nom::error::{ErrorKind, FromExternalError, ParseError as NomParseError};
+use nom::multi::{many0, many1};
+use nom::sequence::{pair, preceded, terminated, tuple};
+use nom::{Err, IResult};
+use nom_locate::LocatedSpan;
 use std::num::ParseIntError;
 
-const CHECKED: &str = "- [X] ";
-const UNCHECKED: &str = "- [ ] ";
+/// Parser input which keeps track of the position in the parsed markdown and carries parse
+/// options.
+pub(crate) type Input<'a> = LocatedSpan<&'a str, &'a ParseOptions>;
 
-pub(crate) fn questions(i: &str) -> IResult<&str, MdQuestions> {
-    let (i, questions) = many1(question)(i)?;
-    Ok((i, MdQuestions::new(questions)))
+/// Parser error which remembers the innermost grammar element that failed.
+#[derive(Debug, PartialEq)]
+pub(crate) struct GrammarError<I> {
+    input: I,
+    kind: ErrorKind,
+    element: Option<Element>,
 }
 
-fn question(i: &str) -> IResult<&str, Question> {
+impl<I> NomParseError<I> for GrammarError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            kind,
+            element: None,
+        }
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<I, E> FromExternalError<I, E> for GrammarError<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _e: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+type Res<'a, O> = IResult<Input<'a>, O, GrammarError<Input<'a>>>;
+
+/// Part of the markdown: either a parsed question or a question skipped because of the `Ignore`
+/// marker.
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum Entry {
+    Question(Question),
+    Ignored(IgnoredQuestion),
+}
+
+pub(crate) fn questions(content: &str, options: &ParseOptions) -> Result<MdQuestions, ParseError> {
+    entries(content, options, |_, e| Err(e)).map(only_questions)
+}
+
+/// Parses all entries of the markdown, keeping the ignored questions.
+pub(crate) fn document(content: &str, options: &ParseOptions) -> Result<Vec<Entry>, ParseError> {
+    entries(content, options, |_, e| Err(e))
+}
+
+/// Parses all questions which follow the convention. Malformed questions are skipped up to the
+/// next question header and reported as diagnostics.
+pub(crate) fn questions_recovering(
+    content: &str,
+    options: &ParseOptions,
+) -> (MdQuestions, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let questions = entries(content, options, |i, e| {
+        let (rest, span) = skipped_question(i).expect("skipping never fails on non-empty input");
+        warn!("skipping malformed question: {}", e);
+        diagnostics.push(Diagnostic::error(span, e.to_string()));
+        Ok(rest)
+    });
+    let questions = questions.expect("recovering parser never fails");
+    (only_questions(questions), diagnostics)
+}
+
+// Runs the question parser until the end of input. On failure, `on_error` decides whether to stop
+// with an error or to continue parsing from the returned input.
+fn entries<'a, F>(
+    content: &'a str,
+    options: &'a ParseOptions,
+    mut on_error: F,
+) -> Result<Vec<Entry>, ParseError>
+where
+    F: FnMut(Input<'a>, ParseError) -> Result<Input<'a>, ParseError>,
+{
+    let mut entries = Vec::new();
+    let mut i = Input::new_extra(content, options);
+    while !i.fragment().trim().is_empty() {
+        match entry(i) {
+            Ok((rest, entry)) => {
+                entries.push(entry);
+                i = rest;
+            }
+            Err(Err::Error(e) | Err::Failure(e)) => i = on_error(i, to_parse_error(i, &e))?,
+            Err(Err::Incomplete(_)) => unreachable!("complete parsers are never incomplete"),
+        }
+    }
+    Ok(entries)
+}
+
+fn only_questions(entries: Vec<Entry>) -> MdQuestions {
+    let mut questions = Vec::new();
+    let mut ignored = Vec::new();
+    for entry in entries {
+        match entry {
+            Entry::Question(question) => questions.push(question),
+            Entry::Ignored(mut question) => {
+                question.position = questions.len();
+                ignored.push(question);
+            }
+        }
+    }
+    MdQuestions::new(questions).with_ignored(ignored)
+}
+
+fn to_parse_error(question_start: Input<'_>, e: &GrammarError<Input<'_>>) -> ParseError {
+    let position = position(&e.input);
+    let (line, column) = (position.line(), position.column());
+    if e.kind == ErrorKind::MapRes {
+        return ParseError::InvalidNumber { line, column };
+    }
+    let question = preceded(many0(eol), number_and_category)(question_start)
+        .ok()
+        .map(|(_, (number, _))| number);
+    ParseError::Syntax {
+        line,
+        column,
+        question,
+        element: e.element.unwrap_or(Element::Header),
+    }
+}
+
+fn position(i: &Input<'_>) -> Position {
+    Position::new(
+        i.location_offset(),
+        i.location_line() as usize,
+        i.get_utf8_column(),
+    )
+}
+
+fn element<'a, O, F>(element: Element, mut parser: F) -> impl FnMut(Input<'a>) -> Res<'a, O>
+where
+    F: FnMut(Input<'a>) -> Res<'a, O>,
+{
+    move |i| {
+        parser(i).map_err(|e| {
+            e.map(|mut e| {
+                e.element.get_or_insert(element);
+                e
+            })
+        })
+    }
+}
+
+fn spanned<'a, O, F>(mut parser: F) -> impl FnMut(Input<'a>) -> Res<'a, (O, Span)>
+where
+    F: FnMut(Input<'a>) -> Res<'a, O>,
+{
+    move |i| {
+        let (rest, o) = parser(i)?;
+        Ok((rest, (o, Span::new(position(&i), position(&rest)))))
+    }
+}
+
+fn entry(i: Input<'_>) -> Res<'_, Entry> {
+    let (i, _) = many0(eol)(i)?;
+    alt((
+        map(ignored_question, Entry::Ignored),
+        map(question, Entry::Question),
+    ))(i)
+}
+
+fn question(i: Input<'_>) -> Res<'_, Question> {
     alt((closed_question, open_question))(i)
 }
 
+// Example of ignored question:
+//
+// ```markdown
+// ## Question 3 `Category 3` `Ignore`
+// Anything up to the questions separator
+//
+// ---
+//
+// ```
+fn ignored_question(i: Input<'_>) -> Res<'_, IgnoredQuestion> {
+    let start = i;
+    let options = i.extra;
+    let (i, (number, category)) = number_and_category(i)?;
+    let (i, markers) = verify(many1(marker), |markers: &[Marker]| {
+        markers.iter().any(|marker| options.skips(marker))
+    })(i)?;
+    debug!("skipping question {} marked with {:?}", number, markers);
+    let (i, _) = cut(element(Element::HorizontalRule, take_until("---")))(i)?;
+    let (i, end) = cut(question_end)(i)?;
+    let span = Span::new(position(&start), end);
+    let raw = start.fragment()[..end.offset() - span.start().offset()].to_string();
+    let question = IgnoredQuestion::new(number, category, markers, raw, span);
+    Ok((i, question))
+}
+
+// Skips the line where parsing failed and everything up to the next question header, or up to the
+// end of input when there is no header left. Resyncing at headers instead of separators keeps the
+// next question when the failure is a stray or a missing separator.
+fn skipped_question(i: Input<'_>) -> Res<'_, Span> {
+    let (i, _) = many0(eol)(i)?;
+    let next_question = recognize(pair(take_until("\n## Question "), newline));
+    let skipped = pair(take_till(|c| c == '\n'), alt((next_question, rest)));
+    let (i, (_, span)) = spanned(recognize(skipped))(i)?;
+    Ok((i, span))
+}
+
 // Example of closed question:
 //
 // ```markdown
@@ -41,31 +242,67 @@ fn question(i: &str) -> IResult<&str, Question> {
 // ---
 //
 // ```
-fn closed_question(i: &str) -> IResult<&str, Question> {
-    let (i, (number, category, text)) = question_prolog(i)?;
-    let (i, _) = answers_header(i)?;
-    let (i, _) = newline(i)?;
-    let (i, answers) = closed_answers(i)?;
-    let (i, reading) = question_epilog(i)?;
+fn closed_question(i: Input<'_>) -> Res<'_, Question> {
+    let start = position(&i);
+    let (i, prolog) = question_prolog(i)?;
+    let (i, _) = element(Element::AnswersHeader, answers_header)(i)?;
+    let (i, ()) = element(Element::AnswersHeader, eol)(i)?;
+    let (i, answers) = cut(element(
+        Element::Checkbox,
+        terminated(closed_answers, end_of_answers),
+    ))(i)?;
+    let (i, explanation) = opt(question_explanation)(i)?;
+    let (i, epilog) = cut(question_epilog)(i)?;
+    let (explanation, explanation_span) = explanation.unzip();
+    let spans = QuestionSpans::new(
+        Span::new(start, epilog.end),
+        prolog.header_span,
+        prolog.text_span,
+        prolog.hints_span,
+        answers_span(&answers),
+        explanation_span,
+        epilog.reading_span,
+    );
     let question = Question::from_closed(
         ClosedQuestionBuilder::default()
-            .number(number)
-            .text(text)
+            .number(prolog.number)
+            .text(prolog.text)
             .answers(answers)
-            .reading(reading)
-            .category(category)
+            .explanation(explanation)
+            .reading(epilog.reading)
+            .category(prolog.category)
+            .markers(prolog.markers)
+            .tags(prolog.tags)
+            .hints(prolog.hints)
             .build()
-            .unwrap(), // TODO: Get rid of this `unwrap`
+            .unwrap() // TODO: Get rid of this `unwrap`
+            .located(&spans),
     );
     debug!("full closed question: {:#?}", question);
     Ok((i, question))
 }
 
+struct Prolog {
+    number: i32,
+    category: String,
+    markers: Vec<Marker>,
+    tags: Vec<String>,
+    text: String,
+    hints: Vec<String>,
+    header_span: Span,
+    text_span: Span,
+    hints_span: Option<Span>,
+}
+
 // Question Prolog
 //
 // ```markdown
 // ## Question 3 `Category 3` <-----
-// Question 3 text                  | - Prolog
+// Tags: `Tag 1` `Tag 2`            | - Prolog (tags and hints are optional)
+// Question 3 text                  |
+//                                  |
+// ## Hints                         |
+// 1. Hint 1                        |
 //                            <-----
 // ## Answers
 // - [ ] Answer 1
@@ -78,12 +315,32 @@ fn closed_question(i: &str) -> IResult<&str, Question> {
 // ---
 //
 // ```
-fn question_prolog(i: &str) -> IResult<&str, (i32, String, String)> {
-    let (i, (number, category)) = question_header(i)?;
-    let (i, _) = newline(i)?;
-    let (i, text) = paragraph(i)?;
-    let (i, _) = empty_line(i)?;
-    Ok((i, (number, category, text)))
+fn question_prolog(i: Input<'_>) -> Res<'_, Prolog> {
+    let (i, ((number, category, markers), header_span)) = spanned(question_header)(i)?;
+    let (i, ()) = element(Element::Prolog, eol)(i)?;
+    let (i, tags) = opt(terminated(tags_line, eol))(i)?;
+    let (i, (text, text_span)) = element(Element::Prolog, spanned(markdown))(i)?;
+    let (i, _) = element(Element::Prolog, empty_line)(i)?;
+    let (i, hints) = opt(spanned(hints_section))(i)?;
+    let (hints, hints_span) = hints.unzip();
+    let prolog = Prolog {
+        number,
+        category,
+        markers,
+        tags: tags.unwrap_or_default(),
+        text,
+        hints: hints.unwrap_or_default(),
+        header_span,
+        text_span,
+        hints_span,
+    };
+    Ok((i, prolog))
+}
+
+struct Epilog {
+    reading: Option<String>,
+    reading_span: Option<Span>,
+    end: Position,
 }
 
 // Question Epilog
@@ -103,13 +360,20 @@ fn question_prolog(i: &str) -> IResult<&str, (i32, String, String)> {
 // ---                               |
 //                             <-----
 // ```
-fn question_epilog(i: &str) -> IResult<&str, Option<String>> {
-    let (i, _) = newline(i)?;
-    let (i, reading) = opt(reading_header)(i)?;
-    let (i, _) = opt(empty_line)(i)?;
-    let (i, _) = horizontal_rule(i)?;
-    let (i, _) = empty_line(i)?;
-    Ok((i, reading))
+fn question_epilog(i: Input<'_>) -> Res<'_, Epilog> {
+    element(Element::Epilog, |i| {
+        let (i, ()) = eol(i)?;
+        let (i, reading) = opt(spanned(reading_header))(i)?;
+        let (i, _) = opt(empty_line)(i)?;
+        let (i, end) = question_end(i)?;
+        let (reading, reading_span) = reading.unzip();
+        let epilog = Epilog {
+            reading,
+            reading_span,
+            end,
+        };
+        Ok((i, epilog))
+    })(i)
 }
 
 // Example of open question:
@@ -123,47 +387,49 @@ fn question_epilog(i: &str) -> IResult<&str, Option<String>> {
 //
 // ## [Reading](Reading 3)
 // ```
-fn open_question(i: &str) -> IResult<&str, Question> {
-    let (i, (number, category, text)) = question_prolog(i)?;
-    let (i, _) = answer_header(i)?;
-    let (i, _) = newline(i)?;
-    let (i, answer) = open_answer(i)?;
-    let (i, reading) = question_epilog(i)?;
+fn open_question(i: Input<'_>) -> Res<'_, Question> {
+    let start = position(&i);
+    let (i, prolog) = question_prolog(i)?;
+    let (i, _) = element(Element::AnswersHeader, answer_header)(i)?;
+    let (i, ()) = element(Element::AnswersHeader, eol)(i)?;
+    let (i, answer) = cut(element(Element::Answer, open_answer))(i)?;
+    let (i, epilog) = cut(question_epilog)(i)?;
+    let answers_span = answer.span().unwrap_or_default();
     let question = Question::from_open(OpenQuestion {
-        number,
-        text,
+        number: prolog.number,
+        text: prolog.text,
         answer,
-        reading,
-        category,
+        reading: epilog.reading,
+        category: prolog.category,
+        markers: prolog.markers,
+        tags: prolog.tags,
+        hints: prolog.hints,
+        location: Location::new(QuestionSpans::new(
+            Span::new(start, epilog.end),
+            prolog.header_span,
+            prolog.text_span,
+            prolog.hints_span,
+            answers_span,
+            None,
+            epilog.reading_span,
+        )),
     });
     debug!("full open question: {:#?}", question);
     Ok((i, question))
 }
 
-fn question_header(i: &str) -> IResult<&str, (i32, String)> {
-    let mut i = i;
-    let (input, num, category) = loop {
-        debug!("loop with input: {}", i);
-        let (input, (num, category)) = number_and_category(i)?;
-        let (input, marker) = opt(marker)(input)?;
-        debug!("found marker: {:#?}", marker);
-        match marker {
-            Some(marker) if marker.to_lowercase() != "ignore" => break (input, num, category),
-            None => break (input, num, category),
-            Some(marker) => warn!("not supported marker: {}", marker),
-        }
-        debug!("ignoring");
-        let (input, _) = take_until("---")(input)?;
-        let (input, _) = horizontal_rule(input)?;
-        let (input, _) = empty_line(input)?;
-        debug!("rest of te input");
-        i = input;
-    };
-
-    Ok((input, (num, category)))
+fn question_header(i: Input<'_>) -> Res<'_, (i32, String, Vec<Marker>)> {
+    element(Element::Header, |i| {
+        let options = i.extra;
+        let (i, (num, category)) = number_and_category(i)?;
+        let (i, markers) = verify(many0(marker), |markers: &[Marker]| {
+            !markers.iter().any(|marker| options.skips(marker))
+        })(i)?;
+        Ok((i, (num, category, markers)))
+    })(i)
 }
 
-fn number_and_category(i: &str) -> IResult<&str, (i32, String)> {
+fn number_and_category(i: Input<'_>) -> Res<'_, (i32, String)> {
     let (i, (_, num, _, category, _)) = tuple((
         tag("## Question "),
         map_res(digit1, to_int),
@@ -171,68 +437,235 @@ fn number_and_category(i: &str) -> IResult<&str, (i32, String)> {
         take_until("`"),
         char('`'),
     ))(i)?;
-    Ok((i, (num, category.into())))
+    Ok((i, (num, category.to_string())))
+}
+
+fn marker(i: Input<'_>) -> Res<'_, Marker> {
+    map(backticked, |marker| Marker::from(marker.as_str()))(i)
+}
+
+// Optional line with tags right after the question header: Tags: `Sling` `exam-2023`
+fn tags_line(i: Input<'_>) -> Res<'_, Vec<String>> {
+    preceded(tag("Tags:"), many1(backticked))(i)
 }
 
-fn marker(i: &str) -> IResult<&str, String> {
-    let (i, (_, marker, _)) = tuple((tag(" `"), take_until("`"), char('`')))(i)?;
-    Ok((i, marker.into()))
+// Space followed by a text in backticks: ` `text``
+fn backticked(i: Input<'_>) -> Res<'_, String> {
+    let (i, (_, text, _)) = tuple((tag(" `"), take_until("`"), char('`')))(i)?;
+    Ok((i, text.to_string()))
 }
 
-fn to_int(i: &str) -> Result<i32, ParseIntError> {
+fn to_int(i: Input<'_>) -> Result<i32, ParseIntError> {
     i.parse::<i32>()
 }
 
-fn empty_line(i: &str) -> IResult<&str, String> {
-    let (i, _) = newline(i)?;
-    let (i, _) = newline(i)?;
+// End of the current line followed by a blank line.
+fn empty_line(i: Input<'_>) -> Res<'_, String> {
+    let (i, ()) = eol(i)?;
+    let (i, ()) = eol(i)?;
     Ok((i, "\n\n".into()))
 }
 
-fn paragraph(i: &str) -> IResult<&str, String> {
-    let (i, text) = take_until("\n\n")(i)?;
-    Ok((i, text.into()))
+// End of line with optional trailing whitespace. Both `\n` and `\r\n` are accepted.
+fn eol(i: Input<'_>) -> Res<'_, ()> {
+    value((), tuple((space0, line_ending)))(i)
+}
+
+// Markdown blocks up to the next section header (`## ...`) or questions separator (`---`). Blank
+// lines are kept and lines inside fenced code blocks never end the text. Trailing blank lines are
+// left for the following parsers. Lines are joined with `\n`.
+fn markdown(i: Input<'_>) -> Res<'_, String> {
+    let (mut i, first) = text_line(i)?;
+    let mut fence = opening_fence(&first);
+    let mut lines = vec![first];
+    let mut blank_lines = 0;
+    let mut next = i;
+    while let Ok((rest, text)) = preceded(eol, line)(next) {
+        next = rest;
+        if text.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        if fence.is_none() && (text.starts_with("## ") || text == "---") {
+            break;
+        }
+        fence = match fence {
+            Some(open) if closes_fence(&text, open) => None,
+            Some(open) => Some(open),
+            None => opening_fence(&text),
+        };
+        lines.resize(lines.len() + blank_lines, String::new());
+        lines.push(text);
+        blank_lines = 0;
+        i = next;
+    }
+    Ok((i, lines.join("\n")))
+}
+
+// Opening fence of a code block: at least three backticks or tildes. Returns the fence character
+// and length.
+fn opening_fence(text: &str) -> Option<(char, usize)> {
+    let text = text.trim_start();
+    let fence = text.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = text.chars().take_while(|c| *c == fence).count();
+    (len >= 3).then_some((fence, len))
+}
+
+fn closes_fence(text: &str, (fence, len): (char, usize)) -> bool {
+    let text = text.trim_start();
+    let closing = text.chars().take_while(|c| *c == fence).count();
+    closing >= len && text[closing * fence.len_utf8()..].trim().is_empty()
+}
+
+fn text_line(i: Input<'_>) -> Res<'_, String> {
+    verify(line, |text: &str| !text.is_empty())(i)
 }
 
-fn line(i: &str) -> IResult<&str, String> {
-    let (i, text) = take_until("\n")(i)?;
-    Ok((i, text.into()))
+// Rest of the current line without the line ending and trailing whitespace.
+fn line(i: Input<'_>) -> Res<'_, String> {
+    let (i, text) = take_till(|c| c == '\n' || c == '\r')(i)?;
+    Ok((i, text.trim_end().to_string()))
 }
 
-fn answers_header(i: &str) -> IResult<&str, &str> {
+fn answers_header(i: Input<'_>) -> Res<'_, Input<'_>> {
     tag("## Answers")(i)
 }
 
-fn answer_header(i: &str) -> IResult<&str, &str> {
+fn answer_header(i: Input<'_>) -> Res<'_, Input<'_>> {
     tag("## Answer")(i)
 }
 
-fn closed_answers(i: &str) -> IResult<&str, Vec<ClosedAnswer>> {
+fn closed_answers(i: Input<'_>) -> Res<'_, Vec<ClosedAnswer>> {
     many1(closed_answer)(i)
 }
 
-fn closed_answer(i: &str) -> IResult<&str, ClosedAnswer> {
-    let (i, (checkbox, text, _)) = tuple((answer_checkbox, line, newline))(i)?;
-    let is_correct = matches!(checkbox, CHECKED);
-    Ok((i, ClosedAnswer::new(text, is_correct)))
+// Closed answer can be wrapped onto continuation lines indented under the checkbox:
+//
+// ```markdown
+// - [X] The bundle providing the service needs to export the java package of the
+//       service interface.
+// ```
+fn closed_answer(i: Input<'_>) -> Res<'_, ClosedAnswer> {
+    let (i, ((checkbox, first_line, continuation), span)) =
+        spanned(tuple((answer_checkbox, line, many0(continuation_line))))(i)?;
+    let (i, explanation) = many0(explanation_line)(i)?;
+    let (i, ()) = eol(i)?;
+    let is_correct = checkbox.contains(['x', 'X']);
+    let text = std::iter::once(first_line)
+        .chain(continuation)
+        .collect::<Vec<_>>()
+        .join(i.extra.line_separator());
+    let mut answer = ClosedAnswer::new(text, is_correct);
+    if !explanation.is_empty() {
+        answer = answer.with_explanation(explanation.join("\n"));
+    }
+    Ok((i, answer.located(span)))
+}
+
+fn continuation_line(i: Input<'_>) -> Res<'_, String> {
+    preceded(tuple((eol, space1, not(char('>')))), text_line)(i)
+}
+
+// Hints section between the question text and answers. Hints can be wrapped like answers:
+//
+// ```markdown
+// ## Hints
+// 1. Hint 1
+// 2. Hint 2
+//    wrapped
+//
+// ```
+fn hints_section(i: Input<'_>) -> Res<'_, Vec<String>> {
+    let (i, _) = tag("## Hints")(i)?;
+    cut(element(Element::Hints, |i| {
+        let (i, ()) = eol(i)?;
+        let (i, hints) = many1(hint)(i)?;
+        let (i, ()) = eol(i)?;
+        Ok((i, hints))
+    }))(i)
+}
+
+fn hint(i: Input<'_>) -> Res<'_, String> {
+    let (i, (_, _, first_line, continuation)) =
+        tuple((digit1, tag(". "), text_line, many0(continuation_line)))(i)?;
+    let (i, ()) = eol(i)?;
+    let text = std::iter::once(first_line)
+        .chain(continuation)
+        .collect::<Vec<_>>()
+        .join(i.extra.line_separator());
+    Ok((i, text))
+}
+
+// Line of the blockquote indented under the answer: `      > Explanation`
+fn explanation_line(i: Input<'_>) -> Res<'_, String> {
+    preceded(tuple((eol, space1, char('>'), opt(char(' ')))), line)(i)
+}
+
+// Explanation section between answers and the epilog. It can contain any markdown blocks:
+//
+// ```markdown
+// ## Explanation
+// Explanation text
+// ```
+fn question_explanation(i: Input<'_>) -> Res<'_, (String, Span)> {
+    let (i, _) = pair(eol, tag("## Explanation"))(i)?;
+    cut(element(Element::Explanation, |i| {
+        let (i, ()) = eol(i)?;
+        let (i, (text, span)) = spanned(markdown)(i)?;
+        let (i, ()) = terminated(eol, end_of_answers)(i)?;
+        Ok((i, (text, span)))
+    }))(i)
+}
+
+fn answers_span(answers: &[ClosedAnswer]) -> Span {
+    let first = answers.first().and_then(ClosedAnswer::span);
+    let last = answers.last().and_then(ClosedAnswer::span);
+    match (first, last) {
+        (Some(first), Some(last)) => Span::new(first.start(), last.end()),
+        _ => Span::default(),
+    }
+}
+
+fn end_of_answers(i: Input<'_>) -> Res<'_, ()> {
+    peek(alt((eol, value((), eof))))(i)
 }
 
-fn open_answer(i: &str) -> IResult<&str, OpenAnswer> {
-    let (i, text) = paragraph(i)?;
-    let (i, _) = newline(i)?;
-    Ok((i, OpenAnswer::new(text)))
+fn open_answer(i: Input<'_>) -> Res<'_, OpenAnswer> {
+    let (i, (text, span)) = spanned(markdown)(i)?;
+    let (i, ()) = eol(i)?;
+    Ok((i, OpenAnswer::new(text).located(span)))
 }
 
-fn answer_checkbox(i: &str) -> IResult<&str, &str> {
-    alt((tag(UNCHECKED), tag(CHECKED)))(i)
+// Checkbox of the closed answer: `- [ ] ` or `- [X] `. Lowercase `x` and `*` or `+` bullets are
+// accepted as well.
+fn answer_checkbox(i: Input<'_>) -> Res<'_, Input<'_>> {
+    recognize(tuple((
+        one_of("-*+"),
+        char(' '),
+        char('['),
+        one_of(" xX"),
+        tag("] "),
+    )))(i)
 }
 
-fn reading_header(i: &str) -> IResult<&str, String> {
+fn reading_header(i: Input<'_>) -> Res<'_, String> {
     let (i, (_, txt, _)) = tuple((tag("## [Reading]("), take_until(")"), tag(")")))(i)?;
-    Ok((i, txt.into()))
+    Ok((i, txt.to_string()))
+}
+
+// Questions separator followed by blank lines. The blank lines after the last separator in the
+// file are optional. Returns position right after the separator.
+fn question_end(i: Input<'_>) -> Res<'_, Position> {
+    element(Element::HorizontalRule, |i| {
+        let (i, _) = horizontal_rule(i)?;
+        let end = position(&i);
+        let (i, ()) = alt((eol, value((), tuple((space0, eof)))))(i)?;
+        let (i, _) = many0(eol)(i)?;
+        Ok((i, end))
+    })(i)
 }
 
-fn horizontal_rule(i: &str) -> IResult<&str, &str> {
+fn horizontal_rule(i: Input<'_>) -> Res<'_, Input<'_>> {
     tag("---")(i)
 }
 
@@ -240,14 +673,45 @@ fn horizontal_rule(i: &str) -> IResult<&str, &str> {
 mod test {
     use super::*;
 
-    use crate::parser::question;
+    use crate::options::LineBreaks;
 
     use anyhow::Result;
     use indoc::indoc;
-    use nom::error::ErrorKind::TakeUntil;
+    use nom::error::ErrorKind::Verify;
     use nom::Err::Error;
     use test_log::test;
 
+    fn parse<'a, O>(
+        parser: impl FnMut(Input<'a>) -> Res<'a, O>,
+        i: &'a str,
+    ) -> Result<(&'a str, O), Err<GrammarError<&'a str>>> {
+        // Parsed fragments borrow the options, so they have to live as long as the input.
+        parse_with(parser, i, Box::leak(Box::default()))
+    }
+
+    fn parse_with<'a, O>(
+        mut parser: impl FnMut(Input<'a>) -> Res<'a, O>,
+        i: &'a str,
+        options: &'a ParseOptions,
+    ) -> Result<(&'a str, O), Err<GrammarError<&'a str>>> {
+        parser(Input::new_extra(i, options))
+            .map(|(rest, o)| (*rest.fragment(), o))
+            .map_err(|e| {
+                e.map(|e| GrammarError {
+                    input: *e.input.fragment(),
+                    kind: e.kind,
+                    element: e.element,
+                })
+            })
+    }
+
+    fn parse_fragment<'a>(
+        parser: impl FnMut(Input<'a>) -> Res<'a, Input<'a>>,
+        i: &'a str,
+    ) -> Result<(&'a str, &'a str), Err<GrammarError<&'a str>>> {
+        parse(parser, i).map(|(rest, o)| (rest, *o.fragment()))
+    }
+
     #[test]
     fn test_questions_parser() -> Result<()> {
         let input = indoc! {"
@@ -289,98 +753,163 @@ mod test {
 
         "};
         assert_eq!(
-            questions(input),
-            Ok((
-                "",
-                MdQuestions::new(vec![
-                    Question::closed()
-                        .number(1)
-                        .text("Question 1 text")
-                        .answers(vec![
-                            ClosedAnswer::incorrect("Answer 1"),
-                            ClosedAnswer::incorrect("Answer 2"),
-                            ClosedAnswer::incorrect("Answer 3"),
-                            ClosedAnswer::correct("Answer 4")
-                        ])
-                        .category("Category 1")
-                        .build()?
-                        .into(),
-                    Question::closed()
-                        .number(2)
-                        .text("Question 2 text")
-                        .answers(vec![
-                            ClosedAnswer::correct("Answer 1"),
-                            ClosedAnswer::incorrect("Answer 2"),
-                            ClosedAnswer::incorrect("Answer 3"),
-                            ClosedAnswer::incorrect("Answer 4"),
-                            ClosedAnswer::correct("Answer 5"),
-                        ])
-                        .category("Category 2")
-                        .build()?
-                        .into(),
-                    Question::closed()
-                        .number(3)
-                        .text("Question 3 text")
-                        .answers(vec![
-                            ClosedAnswer::incorrect("Answer 1"),
-                            ClosedAnswer::correct("Answer 2"),
-                            ClosedAnswer::incorrect("Answer 3"),
-                            ClosedAnswer::incorrect("Answer 4"),
-                        ])
-                        .reading(Some("Reading 3".into()))
-                        .category("Category 3")
-                        .build()?
-                        .into()
-                ])
-            ))
+            questions(input, &ParseOptions::default()),
+            Ok(MdQuestions::new(vec![
+                Question::closed()
+                    .number(1)
+                    .text("Question 1 text")
+                    .answers(vec![
+                        ClosedAnswer::incorrect("Answer 1"),
+                        ClosedAnswer::incorrect("Answer 2"),
+                        ClosedAnswer::incorrect("Answer 3"),
+                        ClosedAnswer::correct("Answer 4")
+                    ])
+                    .category("Category 1")
+                    .build()?
+                    .into(),
+                Question::closed()
+                    .number(2)
+                    .text("Question 2 text")
+                    .answers(vec![
+                        ClosedAnswer::correct("Answer 1"),
+                        ClosedAnswer::incorrect("Answer 2"),
+                        ClosedAnswer::incorrect("Answer 3"),
+                        ClosedAnswer::incorrect("Answer 4"),
+                        ClosedAnswer::correct("Answer 5"),
+                    ])
+                    .category("Category 2")
+                    .build()?
+                    .into(),
+                Question::closed()
+                    .number(3)
+                    .text("Question 3 text")
+                    .answers(vec![
+                        ClosedAnswer::incorrect("Answer 1"),
+                        ClosedAnswer::correct("Answer 2"),
+                        ClosedAnswer::incorrect("Answer 3"),
+                        ClosedAnswer::incorrect("Answer 4"),
+                    ])
+                    .reading(Some("Reading 3".into()))
+                    .category("Category 3")
+                    .build()?
+                    .into()
+            ]))
         );
 
         Ok(())
     }
 
     #[test]
-    fn test_question_parser_with_closed_question() -> Result<()> {
+    fn test_recovering_questions_parser_skips_malformed_questions() -> Result<()> {
         let input = indoc! {"
             ## Question 1 `Category 1`
             Question 1 text
 
             ## Answers
-            - [ ] Answer 1
-            - [ ] Answer 2
-            - [ ] Answer 3
-            - [X] Answer 4
+            - [Y] Answer 1
 
-            ## [Reading](Reading 1)
+            ---
+
+            ## Question 2 `Category 2`
+            Question 2 text
+
+            ## Answer
+            Answer 2
 
             ---
 
+            ## Question 3 `Category 3`
+            Question 3 text
+            ## Answer
+            Answer 3
+
+            ---
         "};
+
+        let (questions, diagnostics) = questions_recovering(input, &ParseOptions::default());
+
         assert_eq!(
-            question(input),
-            Ok((
-                "",
-                Question::closed()
-                    .number(1)
-                    .text("Question 1 text")
-                    .answers(vec![
-                        ClosedAnswer::incorrect("Answer 1"),
-                        ClosedAnswer::incorrect("Answer 2"),
-                        ClosedAnswer::incorrect("Answer 3"),
-                        ClosedAnswer::correct("Answer 4"),
-                    ])
-                    .category("Category 1")
-                    .reading(Some("Reading 1".into()))
-                    .build()?
-                    .into()
-            ))
+            questions,
+            MdQuestions::new(vec![Question::open()
+                .number(2)
+                .text("Question 2 text")
+                .answer(OpenAnswer::new("Answer 2"))
+                .category("Category 2")
+                .build()?
+                .into()])
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error(
+                    Span::new(Position::new(0, 1, 1), Position::new(76, 9, 1)),
+                    "invalid answer checkbox of question 1 at 5:4"
+                ),
+                Diagnostic::error(
+                    Span::new(Position::new(145, 17, 1), Position::new(212, 23, 1)),
+                    "invalid question text of question 3 at 19:1"
+                ),
+            ]
         );
 
         Ok(())
     }
 
     #[test]
-    #[ignore]
-    fn test_question_parser_with_question_metadata() -> Result<()> {
+    fn test_recovering_questions_parser_skips_stray_separator() {
+        let input = indoc! {"
+            ---
+
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answer
+            Answer 1
+
+            ---
+
+            ## Question 2 `Category 2`
+            Question 2 text
+
+            ## Answer
+            Answer 2
+
+            ---
+        "};
+
+        let (questions, diagnostics) = questions_recovering(input, &ParseOptions::default());
+
+        assert_eq!(questions.count(), 2);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                Span::new(Position::new(0, 1, 1), Position::new(5, 3, 1)),
+                "invalid question header at 1:1"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_recovering_questions_parser_without_errors() {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answer
+            Answer 1
+
+            ---
+
+        "};
+
+        let (questions, diagnostics) = questions_recovering(input, &ParseOptions::default());
+
+        assert_eq!(questions.count(), 1);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_question_parser_with_closed_question() -> Result<()> {
         let input = indoc! {"
             ## Question 1 `Category 1`
             Question 1 text
@@ -397,7 +926,7 @@ mod test {
 
         "};
         assert_eq!(
-            question(input),
+            parse(question, input),
             Ok((
                 "",
                 Question::closed()
@@ -420,29 +949,41 @@ mod test {
     }
 
     #[test]
-    fn test_question_parser_with_open_question() -> Result<()> {
+    fn test_question_parser_with_explanations() -> Result<()> {
         let input = indoc! {"
             ## Question 1 `Category 1`
             Question 1 text
 
-            ## Answer
-            Answer
+            ## Answers
+            - [ ] Answer 1
+                  wrapped
+                  > Explanation 1
+                  >
+                  > continued
+            - [X] Answer 2
+                  >Explanation 2
 
-            ## [Reading](Reading 1)
+            ## Explanation
+            Question 1 explanation
 
-            ---
+            Another paragraph
 
+            ---
         "};
         assert_eq!(
-            question(input),
+            parse(question, input),
             Ok((
                 "",
-                Question::open()
+                Question::closed()
                     .number(1)
                     .text("Question 1 text")
-                    .answer(OpenAnswer::new("Answer"))
+                    .answers(vec![
+                        ClosedAnswer::incorrect("Answer 1\nwrapped")
+                            .with_explanation("Explanation 1\n\ncontinued"),
+                        ClosedAnswer::correct("Answer 2").with_explanation("Explanation 2"),
+                    ])
+                    .explanation(Some("Question 1 explanation\n\nAnother paragraph".into()))
                     .category("Category 1")
-                    .reading("Reading 1")
                     .build()?
                     .into()
             ))
@@ -452,37 +993,326 @@ mod test {
     }
 
     #[test]
-    fn test_question_header_parser_with_correct_input() {
-        assert_eq!(
-            question_header("## Question 1 `Templates and Components`"),
-            Ok(("", (1, "Templates and Components".into())))
-        );
-    }
+    fn test_question_parser_with_hints() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
 
-    #[test]
-    fn test_number_and_category_parser_with_correct_input() {
-        assert_eq!(
-            number_and_category("## Question 1 `OSGi Services`"),
-            Ok(("", (1, "OSGi Services".into())))
-        );
-    }
+            ## Hints
+            1. Hint 1
+            2. Hint 2
+               wrapped
 
-    #[test]
-    #[should_panic]
-    fn test_number_and_category_parser_without_number() {
-        number_and_category("## Question `OSGi Services`").unwrap(); // should panic
-    }
+            ## Answer
+            Answer 1
 
-    #[test]
-    #[should_panic]
+            ---
+        "};
+
+        let (_, question) = parse(question, input)?;
+
+        assert_eq!(question.hints(), ["Hint 1", "Hint 2\nwrapped"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_question_parser_with_markdown_blocks() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            What does this print?
+
+            ```rust
+            fn main() {
+
+                // ## Not a header
+                println!(\"{}\", 1 + 1);
+            }
+            ```
+
+            ![Output](img/output.png)
+
+            ## Answer
+            It prints:
+
+            - `2`
+            - and a new line
+
+            ---
+        "};
+
+        let (_, question) = parse(question, input)?;
+
+        let code_blocks = question.text_markdown().code_blocks();
+        assert_eq!(code_blocks.len(), 1);
+        assert_eq!(code_blocks[0].language(), Some("rust"));
+        assert!(code_blocks[0]
+            .code()
+            .contains("\n\n    // ## Not a header\n"));
+        assert!(question
+            .text()
+            .ends_with("```\n\n![Output](img/output.png)"));
+        let answer = question.as_open().unwrap().answer();
+        assert_eq!(
+            answer.markdown().raw(),
+            "It prints:\n\n- `2`\n- and a new line"
+        );
+        assert_eq!(answer.markdown().inline_code(), ["2"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_questions_parser_reports_hints_without_list() {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Hints
+            Hint 1
+
+            ## Answers
+            - [X] Answer 1
+
+            ---
+        "};
+        assert_eq!(
+            questions(input, &ParseOptions::default()),
+            Err(ParseError::Syntax {
+                line: 5,
+                column: 1,
+                question: Some(1),
+                element: Element::Hints
+            })
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_reports_empty_explanation() {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answers
+            - [X] Answer 1
+
+            ## Explanation
+
+            ---
+        "};
+        assert_eq!(
+            questions(input, &ParseOptions::default()),
+            Err(ParseError::Syntax {
+                line: 8,
+                column: 1,
+                question: Some(1),
+                element: Element::Explanation
+            })
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_question_parser_with_question_metadata() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answers
+            - [ ] Answer 1
+            - [ ] Answer 2
+            - [ ] Answer 3
+            - [X] Answer 4
+
+            ## [Reading](Reading 1)
+
+            ---
+
+        "};
+        assert_eq!(
+            parse(question, input),
+            Ok((
+                "",
+                Question::closed()
+                    .number(1)
+                    .text("Question 1 text")
+                    .answers(vec![
+                        ClosedAnswer::incorrect("Answer 1"),
+                        ClosedAnswer::incorrect("Answer 2"),
+                        ClosedAnswer::incorrect("Answer 3"),
+                        ClosedAnswer::correct("Answer 4"),
+                    ])
+                    .category("Category 1")
+                    .reading(Some("Reading 1".into()))
+                    .build()?
+                    .into()
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_question_parser_with_open_question() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answer
+            Answer
+
+            ## [Reading](Reading 1)
+
+            ---
+
+        "};
+        assert_eq!(
+            parse(question, input),
+            Ok((
+                "",
+                Question::open()
+                    .number(1)
+                    .text("Question 1 text")
+                    .answer(OpenAnswer::new("Answer"))
+                    .category("Category 1")
+                    .reading("Reading 1")
+                    .build()?
+                    .into()
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_question_parser_tracks_spans() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answers
+            - [ ] Answer 1
+            - [X] Answer 2
+
+            ## [Reading](Reading 1)
+
+            ---
+
+        "};
+        let (_, question) = parse(question, input)?;
+        let Some(closed) = question.as_closed() else {
+            panic!("Should not happen");
+        };
+        let spans = closed.spans().unwrap();
+
+        assert_eq!(
+            spans.header(),
+            Span::new(Position::new(0, 1, 1), Position::new(26, 1, 27))
+        );
+        assert_eq!(
+            spans.text(),
+            Span::new(Position::new(27, 2, 1), Position::new(42, 2, 16))
+        );
+        assert_eq!(
+            spans.answers(),
+            Span::new(Position::new(55, 5, 1), Position::new(84, 6, 15))
+        );
+        assert_eq!(
+            spans.reading(),
+            Some(Span::new(
+                Position::new(86, 8, 1),
+                Position::new(109, 8, 24)
+            ))
+        );
+        assert_eq!(
+            question.span(),
+            Some(Span::new(Position::new(0, 1, 1), Position::new(114, 10, 4)))
+        );
+        assert_eq!(
+            closed.answer(1).and_then(ClosedAnswer::span),
+            Some(Span::new(Position::new(70, 6, 1), Position::new(84, 6, 15)))
+        );
+        assert_eq!(&input[spans.text().range()], "Question 1 text");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_question_parser_tracks_answer_span() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answer
+            Answer
+
+            ---
+
+        "};
+        let (_, question) = parse(question, input)?;
+        let Some(open) = question.as_open() else {
+            panic!("Should not happen");
+        };
+
+        assert_eq!(
+            open.answer().span(),
+            Some(Span::new(Position::new(54, 5, 1), Position::new(60, 5, 7)))
+        );
+        assert_eq!(open.spans().map(QuestionSpans::reading), Some(None));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_question_header_parser_with_correct_input() {
+        assert_eq!(
+            parse(question_header, "## Question 1 `Templates and Components`"),
+            Ok(("", (1, "Templates and Components".into(), vec![])))
+        );
+    }
+
+    #[test]
+    fn test_question_header_parser_with_markers() {
+        assert_eq!(
+            parse(
+                question_header,
+                "## Question 1 `Category` `draft` `Needs Image`"
+            ),
+            Ok((
+                "",
+                (
+                    1,
+                    "Category".into(),
+                    vec![Marker::Draft, Marker::Custom("Needs Image".into())]
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_number_and_category_parser_with_correct_input() {
+        assert_eq!(
+            parse(number_and_category, "## Question 1 `OSGi Services`"),
+            Ok(("", (1, "OSGi Services".into())))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_number_and_category_parser_without_number() {
+        parse(number_and_category, "## Question `OSGi Services`").unwrap(); // should panic
+    }
+
+    #[test]
+    #[should_panic]
     fn test_number_and_category_parser_without_category() {
-        number_and_category("## Question 1").unwrap(); // should panic
+        parse(number_and_category, "## Question 1").unwrap(); // should panic
     }
 
     #[test]
     fn test_number_and_category_parser_with_empty_category() {
         assert_eq!(
-            number_and_category("## Question 1 ``"),
+            parse(number_and_category, "## Question 1 ``"),
             Ok(("", (1, String::new())))
         );
     }
@@ -490,7 +1320,7 @@ mod test {
     #[test]
     fn test_number_and_category_parser_with_max_number() {
         assert_eq!(
-            number_and_category("## Question 2147483647 `Category`"),
+            parse(number_and_category, "## Question 2147483647 `Category`"),
             Ok(("", (2_147_483_647, "Category".into())))
         );
     }
@@ -498,55 +1328,304 @@ mod test {
     #[test]
     #[should_panic]
     fn test_number_and_category_parser_with_too_big_number() {
-        number_and_category("## Question 4294967296``").unwrap(); // should panic
+        parse(number_and_category, "## Question 4294967296``").unwrap(); // should panic
     }
 
     #[test]
     fn test_marker_parser_with_correct_input() {
-        assert_eq!(marker(" `Ignored`"), Ok(("", "Ignored".into())));
+        assert_eq!(parse(marker, " `Ignored`"), Ok(("", "Ignored".into())));
     }
 
     #[test]
     #[should_panic]
     fn test_marker_parser_with_not_opened_marker() {
-        marker(" Ignored`").unwrap(); // should panic
+        parse(marker, " Ignored`").unwrap(); // should panic
     }
 
     #[test]
     #[should_panic]
     fn test_marker_parser_without_space_in_front() {
-        marker("`Ignored`").unwrap(); // should panic
+        parse(marker, "`Ignored`").unwrap(); // should panic
     }
 
     #[test]
     #[should_panic]
     fn test_marker_parser_with_not_closed_marker() {
-        marker(" `Ignored").unwrap(); // should panic
+        parse(marker, " `Ignored").unwrap(); // should panic
+    }
+
+    #[test]
+    fn test_question_prolog_parser_with_tags() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category`
+            Tags: `Sling` `exam-2023`  
+            Question 1 text
+
+        "};
+
+        let (rest, prolog) = parse(question_prolog, input)?;
+
+        assert_eq!(rest, "");
+        assert_eq!(prolog.tags, vec!["Sling", "exam-2023"]);
+        assert_eq!(prolog.text, "Question 1 text");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tags_line_parser_without_tags() {
+        assert!(parse(tags_line, "Tags: none").is_err());
     }
 
     #[test]
     fn test_question_header_parser_with_ignored_question() {
         assert_eq!(
-            question_header("## Question 1 `OSGi Services` `Ignore`"),
-            Err(Error(nom::error::Error::new("", TakeUntil)))
+            parse(question_header, "## Question 1 `OSGi Services` `Ignore`"),
+            Err(Error(GrammarError {
+                input: " `Ignore`",
+                kind: Verify,
+                element: Some(Element::Header)
+            }))
+        );
+    }
+
+    #[test]
+    fn test_ignored_question_parser() {
+        let input = indoc! {"
+            ## Question 1 `OSGi Services` `Ignore`
+            Question 1 text
+
+            ## Answers
+            - [X] Multi-line
+                  answer 1
+
+            ---
+
+        "};
+        let (rest, question) = parse(ignored_question, input).unwrap();
+
+        assert_eq!(rest, "");
+        assert_eq!(question.number(), 1);
+        assert_eq!(question.category(), "OSGi Services");
+        assert_eq!(question.markers(), &vec![Marker::Ignore]);
+        assert_eq!(question.raw(), input.trim_end());
+        assert_eq!(question.span().map(|span| span.range()), Some(0..103));
+    }
+
+    #[test]
+    fn test_questions_parser_skips_ignored_questions() {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answer
+            Answer 1
+
+            ---
+
+            ## Question 2 `Category 2` `Ignore`
+            Question 2 text
+
+            ---
+        "};
+        assert_eq!(
+            questions(input, &ParseOptions::default()).map(|questions| questions.count()),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_skips_questions_with_configured_markers() -> Result<()> {
+        let input = indoc! {"
+            ## Question 1 `Category 1` `Hard` `Draft`
+            Question 1 text
+
+            ## Answer
+            Answer 1
+
+            ---
+
+            ## Question 2 `Category 2` `Review`
+            Question 2 text
+
+            ## Answer
+            Answer 2
+
+            ---
+
+            ## Question 3 `Category 3` `Ignore`
+            Question 3 text
+
+            ## Answer
+            Answer 3
+
+            ---
+        "};
+        let options = ParseOptions::default().skip_markers([Marker::Draft]);
+
+        let questions = questions(input, &options)?;
+
+        assert_eq!(questions.count(), 2);
+        assert_eq!(questions[0].markers(), [Marker::Review]);
+        assert_eq!(questions[1].markers(), [Marker::Ignore]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_questions_parser_with_empty_input() {
+        assert_eq!(
+            questions("", &ParseOptions::default()),
+            Ok(MdQuestions::default())
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_reports_failing_element() {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answers
+            - [ ] Answer 1
+            - [X] Answer 2
+
+            ---
+
+            ## Question 2 `Category 2`
+            Question 2 text
+
+            ## Answers
+            - [ ] Answer 1
+            - [Y] Answer 2
+
+            ---
+
+        "};
+        assert_eq!(
+            questions(input, &ParseOptions::default()),
+            Err(ParseError::Syntax {
+                line: 15,
+                column: 1,
+                question: Some(2),
+                element: Element::Checkbox,
+            })
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_reports_missing_answers_header() {
+        let input = indoc! {"
+            ## Question 7 `Category 7`
+            Question 7 text
+
+            ## Answerz
+            - [ ] Answer 1
+
+            ---
+
+        "};
+        assert_eq!(
+            questions(input, &ParseOptions::default()),
+            Err(ParseError::Syntax {
+                line: 4,
+                column: 10,
+                question: Some(7),
+                element: Element::AnswersHeader,
+            })
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_reports_question_after_blank_lines() {
+        let input = indoc! {"
+
+            ## Question 4 `Category 4`
+            Question 4 text
+
+            ## Answers
+            -[X] Answer 1
+
+            ---
+        "};
+        assert_eq!(
+            questions(input, &ParseOptions::default()),
+            Err(ParseError::Syntax {
+                line: 6,
+                column: 2,
+                question: Some(4),
+                element: Element::Checkbox,
+            })
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_reports_missing_horizontal_rule() {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answer
+            Answer 1
+
+            ## [Reading](Reading 1)
+
+            ***
+
+        "};
+        assert_eq!(
+            questions(input, &ParseOptions::default()),
+            Err(ParseError::Syntax {
+                line: 9,
+                column: 1,
+                question: Some(1),
+                element: Element::HorizontalRule,
+            })
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_reports_broken_header() {
+        assert_eq!(
+            questions("## Question one `Category 1`\n", &ParseOptions::default()),
+            Err(ParseError::Syntax {
+                line: 1,
+                column: 13,
+                question: None,
+                element: Element::Header,
+            })
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_reports_too_big_number() {
+        assert_eq!(
+            questions(
+                "## Question 4294967296 `Category`\n",
+                &ParseOptions::default()
+            ),
+            Err(ParseError::InvalidNumber {
+                line: 1,
+                column: 13
+            })
         );
     }
 
     #[test]
     fn test_space_between_parser() {
-        assert_eq!(empty_line("\n\n"), Ok(("", "\n\n".into())));
+        assert_eq!(parse(empty_line, "\n\n"), Ok(("", "\n\n".into())));
     }
 
     #[test]
     #[should_panic]
     fn test_space_between_parser_with_text_between_new_lines() {
-        empty_line("\nsome text\n").unwrap(); // should panic
+        parse(empty_line, "\nsome text\n").unwrap(); // should panic
     }
 
     #[test]
     fn test_line_parser() {
         assert_eq!(
-            line("Some text here\n"),
+            parse(line, "Some text here\n"),
             Ok(("\n", "Some text here".into()))
         );
     }
@@ -554,24 +1633,212 @@ mod test {
     #[test]
     fn test_answer_parser() {
         assert_eq!(
-            closed_answer("- [ ] Some answer\n"),
+            parse(closed_answer, "- [ ] Some answer\n"),
             Ok(("", ClosedAnswer::incorrect("Some answer")))
         );
         assert_eq!(
-            closed_answer("- [X] Some answer\n"),
+            parse(closed_answer, "- [X] Some answer\n"),
             Ok(("", ClosedAnswer::correct("Some answer")))
         );
     }
 
+    #[test]
+    fn test_answer_parser_with_continuation_lines() {
+        let input = indoc! {"
+            - [X] Embed both bundles in one content package: the dependency via
+                  Import-Package is enough to ensure correct installation.
+            - [ ] Next answer
+        "};
+        assert_eq!(
+            parse(closed_answer, input),
+            Ok((
+                "- [ ] Next answer\n",
+                ClosedAnswer::correct(
+                    "Embed both bundles in one content package: the dependency via\n\
+                    Import-Package is enough to ensure correct installation."
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_answer_parser_joining_continuation_lines() {
+        let options = ParseOptions::default().line_breaks(LineBreaks::Join);
+        let input = indoc! {"
+            - [ ] Component A calls the HTL of Component B directly using a
+                  `data-sly-include` attribute.
+        "};
+        assert_eq!(
+            parse_with(closed_answer, input, &options),
+            Ok((
+                "",
+                ClosedAnswer::incorrect(
+                    "Component A calls the HTL of Component B directly using a \
+                    `data-sly-include` attribute."
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_answer_parser_tracks_span_of_continuation_lines() -> Result<()> {
+        let input = "- [ ] First\n  second\n";
+        let (_, answer) = parse(closed_answer, input)?;
+        assert_eq!(
+            answer.span(),
+            Some(Span::new(Position::new(0, 1, 1), Position::new(20, 2, 9)))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_answers_parser_with_multi_line_answers() {
+        let input = indoc! {"
+            - [X] Go to Workflow Failures screen and check if any instances of the workflow
+                  are present.
+            - [ ] Go to Workflow Models screen, then delete and recreate the workflow.
+            - [X] Go to Workflow instances screen and verify that the instance of the
+                  workflow is present and check its status.
+        "};
+        assert_eq!(
+            parse(closed_answers, input),
+            Ok((
+                "",
+                vec![
+                    ClosedAnswer::correct(
+                        "Go to Workflow Failures screen and check if any instances of the \
+                        workflow\nare present."
+                    ),
+                    ClosedAnswer::incorrect(
+                        "Go to Workflow Models screen, then delete and recreate the workflow."
+                    ),
+                    ClosedAnswer::correct(
+                        "Go to Workflow instances screen and verify that the instance of the\n\
+                        workflow is present and check its status."
+                    ),
+                ]
+            ))
+        );
+    }
+
     #[test]
     fn test_answers_header_parser() {
-        assert_eq!(answers_header("## Answers\n"), Ok(("\n", "## Answers")));
+        assert_eq!(
+            parse_fragment(answers_header, "## Answers\n"),
+            Ok(("\n", "## Answers"))
+        );
     }
 
     #[test]
     fn test_answer_checkbox() {
-        assert_eq!(answer_checkbox("- [ ] "), Ok(("", "- [ ] ")));
-        assert_eq!(answer_checkbox("- [X] "), Ok(("", "- [X] ")));
+        assert_eq!(
+            parse_fragment(answer_checkbox, "- [ ] "),
+            Ok(("", "- [ ] "))
+        );
+        assert_eq!(
+            parse_fragment(answer_checkbox, "- [X] "),
+            Ok(("", "- [X] "))
+        );
+    }
+
+    #[test]
+    fn test_answer_checkbox_with_lowercase_mark_and_other_bullets() {
+        assert_eq!(
+            parse_fragment(answer_checkbox, "- [x] "),
+            Ok(("", "- [x] "))
+        );
+        assert_eq!(
+            parse_fragment(answer_checkbox, "* [ ] "),
+            Ok(("", "* [ ] "))
+        );
+        assert_eq!(
+            parse_fragment(answer_checkbox, "+ [X] "),
+            Ok(("", "+ [X] "))
+        );
+    }
+
+    #[test]
+    fn test_answer_parser_with_lowercase_mark() {
+        assert_eq!(
+            parse(closed_answer, "- [x] Some answer\n"),
+            Ok(("", ClosedAnswer::correct("Some answer")))
+        );
+        assert_eq!(
+            parse(closed_answer, "* [x] Some answer  \r\n"),
+            Ok(("", ClosedAnswer::correct("Some answer")))
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_with_variations_of_the_convention() -> Result<()> {
+        let input = "\n\
+            ## Question 1 `Category 1`  \r\n\
+            Question 1 text\r\n\
+            second line \r\n\
+            \r\n\
+            ## Answers \r\n\
+            * [ ] Answer 1\r\n\
+            + [x] Answer 2\r\n\
+            \r\n\
+            --- \r\n\
+            \r\n\
+            \r\n\
+            ## Question 2 `Category 2`\n\
+            Question 2 text\n\
+            \t\n\
+            ## Answer\n\
+            Answer 2\n\
+            \n\
+            ---";
+        assert_eq!(
+            questions(input, &ParseOptions::default()),
+            Ok(MdQuestions::new(vec![
+                Question::closed()
+                    .number(1)
+                    .text("Question 1 text\nsecond line")
+                    .answers(vec![
+                        ClosedAnswer::incorrect("Answer 1"),
+                        ClosedAnswer::correct("Answer 2"),
+                    ])
+                    .category("Category 1")
+                    .build()?
+                    .into(),
+                Question::open()
+                    .number(2)
+                    .text("Question 2 text")
+                    .answer(OpenAnswer::new("Answer 2"))
+                    .category("Category 2")
+                    .build()?
+                    .into(),
+            ]))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_questions_parser_without_new_line_at_the_end() {
+        let input = indoc! {"
+            ## Question 1 `Category 1`
+            Question 1 text
+
+            ## Answer
+            Answer 1
+
+            ---"};
+        assert_eq!(
+            questions(input, &ParseOptions::default()).map(|questions| questions.count()),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_questions_parser_with_only_blank_lines() {
+        assert_eq!(
+            questions("\n  \n\r\n", &ParseOptions::default()),
+            Ok(MdQuestions::default())
+        );
     }
 
     #[test]
@@ -583,7 +1850,7 @@ mod test {
             - [X] Inherit from the teaser core component.
         "};
         assert_eq!(
-            closed_answers(input),
+            parse(closed_answers, input),
             Ok((
                 "",
                 vec![
@@ -602,7 +1869,7 @@ mod test {
             - [X] Use and configure the teaser core component.
         "};
         assert_eq!(
-            closed_answers(input),
+            parse(closed_answers, input),
             Ok((
                 "",
                 vec![ClosedAnswer::correct(
@@ -615,7 +1882,10 @@ mod test {
     #[test]
     fn test_reading_header_parser_with_correct_input() {
         assert_eq!(
-            reading_header("## [Reading](reading/question-3-reading.md)\n"),
+            parse(
+                reading_header,
+                "## [Reading](reading/question-3-reading.md)\n"
+            ),
             Ok(("\n", "reading/question-3-reading.md".into()))
         );
     }
@@ -623,50 +1893,72 @@ mod test {
     #[test]
     #[should_panic]
     fn test_reading_header_parser_with_url_not_closed() {
-        reading_header("## [Reading](reading/question-3-reading.md\n").unwrap();
+        parse(
+            reading_header,
+            "## [Reading](reading/question-3-reading.md\n",
+        )
+        .unwrap();
         // should panic
     }
 
     #[test]
     #[should_panic]
     fn test_reading_header_parser_with_url_not_opened() {
-        reading_header("## [Reading]reading/question-3-reading.md)\n").unwrap();
+        parse(
+            reading_header,
+            "## [Reading]reading/question-3-reading.md)\n",
+        )
+        .unwrap();
         // should panic
     }
 
     #[test]
     #[should_panic]
     fn test_reading_header_parser_with_label_not_opened() {
-        reading_header("## Reading](reading/question-3-reading.md)\n").unwrap();
+        parse(
+            reading_header,
+            "## Reading](reading/question-3-reading.md)\n",
+        )
+        .unwrap();
         // should panic
     }
 
     #[test]
     #[should_panic]
     fn test_reading_header_parser_with_label_not_closed() {
-        reading_header("## [Reading(reading/question-3-reading.md)\n").unwrap();
+        parse(
+            reading_header,
+            "## [Reading(reading/question-3-reading.md)\n",
+        )
+        .unwrap();
         // should panic
     }
 
     #[test]
     fn test_reading_header_parser_with_broken_reading_header() {
-        let res = reading_header("## [Reading-broken](reading/question-3-reading.md)\n");
+        let res = parse(
+            reading_header,
+            "## [Reading-broken](reading/question-3-reading.md)\n",
+        );
         assert!(res.is_err());
 
-        let res = reading_header("## [Reading-broken](reading/question-3-reading.md)\n");
+        let res = parse(
+            reading_header,
+            "## [Reading-broken](reading/question-3-reading.md)\n",
+        );
         assert!(res.is_err());
     }
 
     #[test]
     fn test_reading_header_parser_with_empty_url() {
         assert_eq!(
-            reading_header("## [Reading]()\n"),
+            parse(reading_header, "## [Reading]()\n"),
             Ok(("\n", String::new()))
         );
     }
 
     #[test]
     fn test_horizontal_rule_parser() {
-        assert_eq!(horizontal_rule("---\n"), Ok(("\n", "---")));
+        assert_eq!(parse_fragment(horizontal_rule, "---\n"), Ok(("\n", "---")));
     }
 }
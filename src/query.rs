@@ -0,0 +1,176 @@
+use crate::marker::Marker;
+use crate::question::Question;
+use crate::MdQuestions;
+
+/// Conditions which questions have to meet, used by [`MdQuestions::query`].
+///
+/// All conditions have to be met. The default query matches all questions.
+///
+/// ```
+/// # use md_questions::{Marker, Query};
+/// let query = Query::default()
+///     .category("OSGi Services")
+///     .tag("exam-2023")
+///     .marker(Marker::Hard)
+///     .multi(true)
+///     .text_contains("bundle");
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Query {
+    category: Option<String>,
+    tags: Vec<String>,
+    markers: Vec<Marker>,
+    multi: Option<bool>,
+    text: Option<String>,
+}
+
+impl Query {
+    /// Matches questions from the category.
+    #[must_use]
+    pub fn category<S: Into<String>>(mut self, category: S) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Matches questions having the tag. Can be used many times to require many tags.
+    #[must_use]
+    pub fn tag<S: Into<String>>(mut self, tag: S) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Matches questions having the marker. Can be used many times to require many markers.
+    #[must_use]
+    pub fn marker(mut self, marker: Marker) -> Self {
+        self.markers.push(marker);
+        self
+    }
+
+    /// Matches closed questions with many correct answers (`true`) or with a single correct
+    /// answer (`false`). Open questions are never matched.
+    #[must_use]
+    pub fn multi(mut self, multi: bool) -> Self {
+        self.multi = Some(multi);
+        self
+    }
+
+    /// Matches questions whose text contains the phrase, ignoring case.
+    #[must_use]
+    pub fn text_contains<S: Into<String>>(mut self, phrase: S) -> Self {
+        self.text = Some(phrase.into().to_lowercase());
+        self
+    }
+
+    // `Option::is_none_or` would need Rust 1.82.
+    #[must_use]
+    #[allow(clippy::unnecessary_map_or)]
+    pub fn matches(&self, question: &Question) -> bool {
+        let multi = |multi: bool| question.as_closed().is_some_and(|q| q.is_multi() == multi);
+        let contains = |phrase: &String| question.text().to_lowercase().contains(phrase);
+        self.category
+            .as_ref()
+            .map_or(true, |category| question.category() == category)
+            && question.has_all_tags(&self.tags)
+            && self
+                .markers
+                .iter()
+                .all(|marker| question.has_marker(marker))
+            && self.multi.map_or(true, multi)
+            && self.text.as_ref().map_or(true, contains)
+    }
+}
+
+impl MdQuestions {
+    /// Questions matching the query.
+    pub fn query<'a>(&'a self, query: &'a Query) -> impl Iterator<Item = &'a Question> + 'a {
+        self.questions.iter().filter(|q| query.matches(q))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_default_query_matches_all_questions() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi`
+            Which bundle is active?
+
+            ## Answers
+            - [X] a
+
+            ---
+
+            ## Question 2 `OSGi`
+            Describe services.
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+
+        let numbers = questions
+            .query(&Query::default())
+            .map(Question::number)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, [1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_combines_conditions() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi` `Hard`
+            Tags: `exam-2023`
+            Which TWO bundles are active?
+
+            ## Answers
+            - [X] a
+            - [X] b
+
+            ---
+
+            ## Question 2 `OSGi`
+            Which bundle is active?
+
+            ## Answers
+            - [X] a
+
+            ---
+
+            ## Question 3 `OSGi`
+            Tags: `exam-2023`
+            Describe services.
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+        let numbers = |query: &Query| {
+            questions
+                .query(query)
+                .map(Question::number)
+                .collect::<Vec<_>>()
+        };
+
+        let exam = Query::default().category("OSGi").tag("exam-2023");
+        let hard = exam.clone().marker(Marker::Hard);
+        let single = Query::default().multi(false);
+        let bundles = Query::default().text_contains("BUNDLE");
+
+        assert_eq!(numbers(&exam), [1, 3]);
+        assert_eq!(numbers(&hard), [1]);
+        assert_eq!(numbers(&single), [2]);
+        assert_eq!(numbers(&bundles), [1, 2]);
+        assert_eq!(numbers(&bundles.multi(true)), [1]);
+
+        Ok(())
+    }
+}
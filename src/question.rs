@@ -1,25 +1,39 @@
 use crate::answer::{ClosedAnswer, OpenAnswer};
+use crate::markdown::Markdown;
+use crate::marker::Marker;
+use crate::span::{Location, QuestionSpans, Span};
 
 use derive_builder::Builder;
-use either::Either;
 use getset::Getters;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct Question {
-    q: Either<ClosedQuestion, OpenQuestion>,
+/// Question parsed from the markdown.
+///
+/// New kinds of questions can be added in the future, so matching on it requires a wildcard arm.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum Question {
+    /// Question with answers to choose from.
+    Closed(ClosedQuestion),
+    /// Question with a free-form answer.
+    Open(OpenQuestion),
 }
 
 impl Question {
     #[must_use]
     pub fn from_closed(q: ClosedQuestion) -> Self {
-        Self { q: Either::Left(q) }
+        Self::Closed(q)
     }
 
     #[must_use]
     pub fn from_open(q: OpenQuestion) -> Self {
-        Self {
-            q: Either::Right(q),
-        }
+        Self::Open(q)
     }
 
     #[must_use]
@@ -34,48 +48,216 @@ impl Question {
 
     #[must_use]
     pub fn is_closed(&self) -> bool {
-        self.q.is_right()
+        matches!(self, Self::Closed(_))
+    }
+
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open(_))
+    }
+
+    #[must_use]
+    pub fn as_closed(&self) -> Option<&ClosedQuestion> {
+        match self {
+            Self::Closed(q) => Some(q),
+            Self::Open(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_open(&self) -> Option<&OpenQuestion> {
+        match self {
+            Self::Open(q) => Some(q),
+            Self::Closed(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn into_closed(self) -> Option<ClosedQuestion> {
+        match self {
+            Self::Closed(q) => Some(q),
+            Self::Open(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn into_open(self) -> Option<OpenQuestion> {
+        match self {
+            Self::Open(q) => Some(q),
+            Self::Closed(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn number(&self) -> i32 {
+        match self {
+            Self::Closed(q) => q.number,
+            Self::Open(q) => q.number,
+        }
+    }
+
+    #[must_use]
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Closed(q) => &q.text,
+            Self::Open(q) => &q.text,
+        }
+    }
+
+    /// Question text as [`Markdown`].
+    #[must_use]
+    pub fn text_markdown(&self) -> Markdown<'_> {
+        Markdown::new(self.text())
+    }
+
+    #[must_use]
+    pub fn category(&self) -> &str {
+        match self {
+            Self::Closed(q) => &q.category,
+            Self::Open(q) => &q.category,
+        }
+    }
+
+    #[must_use]
+    pub fn reading(&self) -> Option<&str> {
+        match self {
+            Self::Closed(q) => q.reading.as_deref(),
+            Self::Open(q) => q.reading.as_deref(),
+        }
+    }
+
+    /// Markers of the question header which didn't cause the question to be skipped.
+    #[must_use]
+    pub fn markers(&self) -> &[Marker] {
+        match self {
+            Self::Closed(q) => &q.markers,
+            Self::Open(q) => &q.markers,
+        }
+    }
+
+    #[must_use]
+    pub fn has_marker(&self, marker: &Marker) -> bool {
+        self.markers().contains(marker)
+    }
+
+    /// Hints listed in the `## Hints` section, in the order they should be revealed.
+    #[must_use]
+    pub fn hints(&self) -> &[String] {
+        match self {
+            Self::Closed(q) => q.hints(),
+            Self::Open(q) => q.hints(),
+        }
+    }
+
+    /// Tags listed in the `Tags:` line below the question header.
+    #[must_use]
+    pub fn tags(&self) -> &[String] {
+        match self {
+            Self::Closed(q) => &q.tags,
+            Self::Open(q) => &q.tags,
+        }
     }
 
     #[must_use]
-    pub fn as_closed(&self) -> Option<ClosedQuestion> {
-        self.q.clone().left()
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags().iter().any(|t| t == tag)
     }
 
+    /// Checks whether the question has at least one of given tags.
     #[must_use]
-    pub fn as_open(&self) -> Option<OpenQuestion> {
-        self.q.clone().right()
+    pub fn has_any_tag<S: AsRef<str>>(&self, tags: &[S]) -> bool {
+        tags.iter().any(|tag| self.has_tag(tag.as_ref()))
+    }
+
+    /// Checks whether the question has all given tags.
+    #[must_use]
+    pub fn has_all_tags<S: AsRef<str>>(&self, tags: &[S]) -> bool {
+        tags.iter().all(|tag| self.has_tag(tag.as_ref()))
+    }
+
+    /// Span of the whole question. Available only for parsed questions.
+    #[must_use]
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::Closed(q) => q.span(),
+            Self::Open(q) => q.span(),
+        }
+    }
+
+    /// Spans of the question parts. Available only for parsed questions.
+    #[must_use]
+    pub fn spans(&self) -> Option<&QuestionSpans> {
+        match self {
+            Self::Closed(q) => q.spans(),
+            Self::Open(q) => q.spans(),
+        }
+    }
+
+    pub(crate) fn set_number(&mut self, number: i32) {
+        match self {
+            Self::Closed(q) => q.number = number,
+            Self::Open(q) => q.number = number,
+        }
+    }
+
+    pub(crate) fn reading_mut(&mut self) -> &mut Option<String> {
+        match self {
+            Self::Closed(q) => &mut q.reading,
+            Self::Open(q) => &mut q.reading,
+        }
     }
 }
 
 impl From<ClosedQuestion> for Question {
     fn from(q: ClosedQuestion) -> Self {
-        Self { q: Either::Left(q) }
+        Self::Closed(q)
     }
 }
 
 impl From<OpenQuestion> for Question {
     fn from(q: OpenQuestion) -> Self {
-        Self {
-            q: Either::Right(q),
-        }
+        Self::Open(q)
     }
 }
 
 #[derive(Default, Getters, Builder, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[get = "pub"]
 #[builder(setter(into))]
 pub struct ClosedQuestion {
-    number: i32,
+    pub(crate) number: i32,
+
+    pub(crate) text: String,
+
+    pub(crate) answers: Vec<ClosedAnswer>,
+
+    /// Text of the `## Explanation` section.
+    #[builder(setter(into), default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) explanation: Option<String>,
+
+    #[builder(setter(into), default)]
+    pub(crate) reading: Option<String>,
 
-    text: String,
+    pub(crate) category: String,
 
-    answers: Vec<ClosedAnswer>,
+    #[builder(setter(into), default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) markers: Vec<Marker>,
 
     #[builder(setter(into), default)]
-    reading: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tags: Vec<String>,
 
-    category: String,
+    #[getset(skip)]
+    #[builder(setter(into), default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) hints: Vec<String>,
+
+    #[getset(skip)]
+    #[builder(setter(skip))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) location: Location<QuestionSpans>,
 }
 
 impl ClosedQuestion {
@@ -91,16 +273,94 @@ impl ClosedQuestion {
 
     #[must_use]
     pub fn is_multi(&self) -> bool {
-        let correct_answers = self
-            .answers
+        self.correct_count() > 1
+    }
+
+    /// Number of correct answers.
+    #[must_use]
+    pub fn correct_count(&self) -> usize {
+        self.answers
             .iter()
             .filter(|&answer| answer.is_correct())
-            .count();
-        correct_answers > 1
+            .count()
+    }
+
+    /// Number of answers to choose stated in the question text, e.g. `2` for "Which two options
+    /// are correct? (Choose two.)".
+    ///
+    /// Counts written as digits or English words up to ten are recognised after "choose",
+    /// "select" and "pick". Counts after "which" are used only when there is no such phrase.
+    /// Open-ended counts like "one or more", "at least two" or "up to three" give `None`.
+    #[must_use]
+    pub fn expected_choices(&self) -> Option<usize> {
+        let text = self.text.to_lowercase();
+        let words = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>();
+        let open_ended = words.windows(3).any(|triple| match triple {
+            ["at", "least", count] | ["up", "to", count] | [count, "or", "more"] => {
+                choice_count(count).is_some()
+            }
+            _ => false,
+        });
+        if open_ended {
+            return None;
+        }
+        let count_after = |verbs: &[&str]| {
+            words
+                .windows(2)
+                .filter(|pair| verbs.contains(&pair[0]))
+                .find_map(|pair| choice_count(pair[1]))
+        };
+        count_after(&["choose", "select", "pick"]).or_else(|| count_after(&["which"]))
+    }
+
+    /// Question text as [`Markdown`].
+    #[must_use]
+    pub fn text_markdown(&self) -> Markdown<'_> {
+        Markdown::new(&self.text)
+    }
+
+    /// Hints listed in the `## Hints` section, in the order they should be revealed.
+    #[must_use]
+    pub fn hints(&self) -> &[String] {
+        &self.hints
+    }
+
+    /// Span of the whole question. Available only for parsed questions.
+    #[must_use]
+    pub fn span(&self) -> Option<Span> {
+        self.spans().map(QuestionSpans::question)
+    }
+
+    /// Spans of the question parts. Available only for parsed questions.
+    #[must_use]
+    pub fn spans(&self) -> Option<&QuestionSpans> {
+        self.location.get()
     }
+
+    pub(crate) fn located(mut self, spans: &QuestionSpans) -> Self {
+        self.location = Location::new(*spans);
+        self
+    }
+}
+
+const CHOICE_COUNTS: [&str; 10] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+];
+
+fn choice_count(word: &str) -> Option<usize> {
+    word.parse().ok().filter(|&count| count > 0).or_else(|| {
+        CHOICE_COUNTS
+            .iter()
+            .position(|&name| name == word)
+            .map(|idx| idx + 1)
+    })
 }
 
 #[derive(Default, Builder, Getters, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[get = "pub"]
 pub struct OpenQuestion {
     pub(crate) number: i32,
@@ -115,4 +375,102 @@ pub struct OpenQuestion {
 
     #[builder(setter(into))]
     pub(crate) category: String,
+
+    #[builder(setter(into), default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) markers: Vec<Marker>,
+
+    #[builder(setter(into), default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tags: Vec<String>,
+
+    #[getset(skip)]
+    #[builder(setter(into), default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) hints: Vec<String>,
+
+    #[getset(skip)]
+    #[builder(setter(skip))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) location: Location<QuestionSpans>,
+}
+
+impl OpenQuestion {
+    /// Question text as [`Markdown`].
+    #[must_use]
+    pub fn text_markdown(&self) -> Markdown<'_> {
+        Markdown::new(&self.text)
+    }
+
+    /// Hints listed in the `## Hints` section, in the order they should be revealed.
+    #[must_use]
+    pub fn hints(&self) -> &[String] {
+        &self.hints
+    }
+
+    /// Span of the whole question. Available only for parsed questions.
+    #[must_use]
+    pub fn span(&self) -> Option<Span> {
+        self.spans().map(QuestionSpans::question)
+    }
+
+    /// Spans of the question parts. Available only for parsed questions.
+    #[must_use]
+    pub fn spans(&self) -> Option<&QuestionSpans> {
+        self.location.get()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::Result;
+
+    fn question(text: &str) -> Result<ClosedQuestion> {
+        Ok(Question::closed()
+            .number(1)
+            .text(text)
+            .answers(vec![
+                ClosedAnswer::correct("Answer 1"),
+                ClosedAnswer::correct("Answer 2"),
+                ClosedAnswer::incorrect("Answer 3"),
+            ])
+            .category("Category")
+            .build()?)
+    }
+
+    #[test]
+    fn test_counting_correct_answers() -> Result<()> {
+        let question = question("Text")?;
+
+        assert_eq!(question.correct_count(), 2);
+        assert!(question.is_multi());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expected_choices() -> Result<()> {
+        let cases = [
+            ("Which two options are correct? (Choose two.)", Some(2)),
+            ("What is correct?\n(Choose 3)", Some(3)),
+            ("Select ONE answer.", Some(1)),
+            ("Which three statements are true?", Some(3)),
+            ("Which one is wrong? Pick two.", Some(2)),
+            ("Which option is correct?", None),
+            ("Choose wisely.", None),
+            ("Choose 0 answers.", None),
+            ("Select one or more answers.", None),
+            ("Which two or more options apply?", None),
+            ("Choose at least two.", None),
+            ("Pick up to 3 answers.", None),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(question(text)?.expected_choices(), expected, "{text}");
+        }
+
+        Ok(())
+    }
 }
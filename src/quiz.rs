@@ -0,0 +1,578 @@
+//! Quiz sessions: presenting questions one by one, collecting responses and grading them.
+//!
+//! [`QuizSession`] goes through a selection of questions, e.g. all questions, a
+//! [sample](MdQuestions::sample) or the result of a [query](MdQuestions::query). Responses to
+//! closed questions are graded right away, responses to open questions have to be graded with
+//! [`QuizSession::grade`]. [`QuizSession::result`] summarises the scores:
+//!
+//! ```
+//! use md_questions::quiz::{QuizSession, Response};
+//! use md_questions::MdQuestions;
+//!
+//! let questions = MdQuestions::parse("")?;
+//! let mut session = QuizSession::new(questions.sample(10, 42));
+//! while let Some(question) = session.current() {
+//!     let response = match question.as_closed() {
+//!         Some(_) => Response::from(vec![0]),
+//!         None => Response::from("My answer"),
+//!     };
+//!     session.respond(response)?;
+//! }
+//! let result = session.result();
+//! println!("{} of {} points", result.score(), result.max_score());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::question::{ClosedQuestion, Question};
+use crate::MdQuestions;
+
+use getset::{CopyGetters, Getters};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Response to a question: indices of the chosen answers of a closed question, or the text
+/// answering an open question.
+///
+/// With the `serde` feature enabled, closed responses are serialized as arrays and open responses
+/// as strings.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(untagged))]
+pub enum Response {
+    Closed(Vec<usize>),
+    Open(String),
+}
+
+impl From<Vec<usize>> for Response {
+    fn from(answers: Vec<usize>) -> Self {
+        Self::Closed(answers)
+    }
+}
+
+impl From<String> for Response {
+    fn from(text: String) -> Self {
+        Self::Open(text)
+    }
+}
+
+impl From<&str> for Response {
+    fn from(text: &str) -> Self {
+        Self::Open(text.into())
+    }
+}
+
+/// How responses to closed questions are scored. Each question is worth 1 point.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum Scoring {
+    /// 1 point when exactly the correct answers are chosen, 0 otherwise.
+    #[default]
+    AllOrNothing,
+    /// Share of the correct answers chosen, minus the same share for each incorrect answer
+    /// chosen, but not less than 0. E.g. choosing 1 of 2 correct answers gives 0.5 points.
+    Partial,
+}
+
+/// Error returned when a response or a grade can't be accepted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum QuizError {
+    /// All questions have been responded to, there's no current question.
+    Finished,
+    /// There's no question at given index.
+    OutOfRange { index: usize },
+    /// Closed response to an open question or the other way round, or grading of a closed
+    /// question.
+    WrongKind { question: i32 },
+    /// Chosen answer doesn't exist.
+    InvalidAnswer { question: i32, answer: usize },
+    /// Open question can't be graded before it's responded to.
+    NoResponse { question: i32 },
+    /// Score is not a finite number.
+    InvalidScore { question: i32 },
+}
+
+impl Display for QuizError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Finished => write!(f, "quiz is finished"),
+            Self::OutOfRange { index } => write!(f, "no question at index {index}"),
+            Self::WrongKind { question } => {
+                write!(f, "wrong kind of response to question {question}")
+            }
+            Self::InvalidAnswer { question, answer } => {
+                write!(f, "question {question} has no answer at index {answer}")
+            }
+            Self::NoResponse { question } => write!(f, "question {question} has no response"),
+            Self::InvalidScore { question } => {
+                write!(f, "invalid score of question {question}")
+            }
+        }
+    }
+}
+
+impl Error for QuizError {}
+
+/// Quiz over a selection of questions, keeping the responses and their scores.
+#[derive(Debug, Clone)]
+pub struct QuizSession<'a> {
+    questions: Vec<&'a Question>,
+    responses: Vec<Option<Response>>,
+    scores: Vec<Option<f64>>,
+    position: usize,
+    scoring: Scoring,
+}
+
+impl<'a> QuizSession<'a> {
+    /// Starts a quiz at the first of the questions.
+    pub fn new<I: IntoIterator<Item = &'a Question>>(questions: I) -> Self {
+        let questions = questions.into_iter().collect::<Vec<_>>();
+        Self {
+            responses: vec![None; questions.len()],
+            scores: vec![None; questions.len()],
+            questions,
+            position: 0,
+            scoring: Scoring::default(),
+        }
+    }
+
+    /// Sets how responses to closed questions are scored. It's [`Scoring::AllOrNothing`] by
+    /// default. Responses given before are scored again.
+    #[must_use]
+    pub fn scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        for idx in 0..self.questions.len() {
+            if let (Some(q), Some(Response::Closed(answers))) =
+                (self.questions[idx].as_closed(), &self.responses[idx])
+            {
+                self.scores[idx] = Some(score(q, answers, scoring));
+            }
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn questions(&self) -> &[&'a Question] {
+        &self.questions
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.questions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.questions.is_empty()
+    }
+
+    /// Index of the current question. Equal to [`len`](Self::len) when the quiz is finished.
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Question to respond to, `None` when the quiz is finished.
+    #[must_use]
+    pub fn current(&self) -> Option<&'a Question> {
+        self.questions.get(self.position).copied()
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.questions.len()
+    }
+
+    /// Moves to the question at given index, e.g. to change the response to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QuizError::OutOfRange`] when there's no question at the index.
+    pub fn go_to(&mut self, index: usize) -> Result<(), QuizError> {
+        if index >= self.questions.len() {
+            return Err(QuizError::OutOfRange { index });
+        }
+        self.position = index;
+        Ok(())
+    }
+
+    /// Moves to the next question without responding to the current one.
+    pub fn skip(&mut self) {
+        self.position = (self.position + 1).min(self.questions.len());
+    }
+
+    /// Responds to the current question and moves to the next one. Returns the score of a
+    /// response to a closed question, `None` for open questions which are graded with
+    /// [`grade`](Self::grade).
+    ///
+    /// Chosen answers are indices in [`ClosedQuestion::answers`], their order and repetitions
+    /// don't matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the quiz is finished, when the kind of the response doesn't match
+    /// the question or when a chosen answer doesn't exist. The position doesn't change then.
+    pub fn respond<R: Into<Response>>(&mut self, response: R) -> Result<Option<f64>, QuizError> {
+        let question = self.current().ok_or(QuizError::Finished)?;
+        let number = question.number();
+        let mut response = response.into();
+        let score = match (question, &mut response) {
+            (Question::Closed(q), Response::Closed(answers)) => {
+                answers.sort_unstable();
+                answers.dedup();
+                if let Some(&answer) = answers.iter().find(|&&idx| idx >= q.answers_count()) {
+                    return Err(QuizError::InvalidAnswer {
+                        question: number,
+                        answer,
+                    });
+                }
+                Some(score(q, answers, self.scoring))
+            }
+            (Question::Open(_), Response::Open(_)) => None,
+            _ => return Err(QuizError::WrongKind { question: number }),
+        };
+        self.responses[self.position] = Some(response);
+        self.scores[self.position] = score;
+        self.position += 1;
+        Ok(score)
+    }
+
+    /// Grades the response to the open question at given index with a score from 0 to 1. Scores
+    /// out of this range are clamped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when there's no question at the index, when it's a closed question, when
+    /// it has no response yet or when the score is not finite.
+    pub fn grade(&mut self, index: usize, score: f64) -> Result<(), QuizError> {
+        let question = self
+            .questions
+            .get(index)
+            .ok_or(QuizError::OutOfRange { index })?;
+        let number = question.number();
+        if question.is_closed() {
+            return Err(QuizError::WrongKind { question: number });
+        }
+        if self.responses[index].is_none() {
+            return Err(QuizError::NoResponse { question: number });
+        }
+        if !score.is_finite() {
+            return Err(QuizError::InvalidScore { question: number });
+        }
+        self.scores[index] = Some(score.clamp(0.0, 1.0));
+        Ok(())
+    }
+
+    /// Response to the question at given index.
+    #[must_use]
+    pub fn response(&self, index: usize) -> Option<&Response> {
+        self.responses.get(index)?.as_ref()
+    }
+
+    /// Score of the question at given index, `None` when it has no response or when the response
+    /// to an open question isn't graded.
+    #[must_use]
+    pub fn score(&self, index: usize) -> Option<f64> {
+        self.scores.get(index).copied().flatten()
+    }
+
+    /// Summary of the responses and scores. Questions without responses score 0 points.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn result(&self) -> QuizResult {
+        let questions = self
+            .questions
+            .iter()
+            .zip(&self.responses)
+            .zip(&self.scores)
+            .map(|((question, response), &score)| QuestionResult {
+                number: question.number(),
+                category: question.category().to_string(),
+                response: response.clone(),
+                score: score.or(response.is_none().then_some(0.0)),
+            })
+            .collect::<Vec<_>>();
+        QuizResult {
+            score: questions.iter().filter_map(|q| q.score).sum(),
+            max_score: questions.len() as f64,
+            questions,
+        }
+    }
+}
+
+impl MdQuestions {
+    /// Starts a quiz over all questions, in their order.
+    #[must_use]
+    pub fn quiz(&self) -> QuizSession<'_> {
+        QuizSession::new(self)
+    }
+}
+
+/// Response and score of a single question of [`QuizResult`].
+#[derive(Debug, Getters, CopyGetters, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuestionResult {
+    #[getset(get_copy = "pub")]
+    number: i32,
+
+    #[getset(get = "pub")]
+    category: String,
+
+    #[getset(skip)]
+    response: Option<Response>,
+
+    /// Score from 0 to 1, `None` when the response to an open question isn't graded.
+    #[getset(get_copy = "pub")]
+    score: Option<f64>,
+}
+
+impl QuestionResult {
+    #[must_use]
+    pub fn response(&self) -> Option<&Response> {
+        self.response.as_ref()
+    }
+}
+
+/// Summary of a [`QuizSession`].
+///
+/// With the `serde` feature enabled, the summary can be serialized, e.g. to store the results.
+#[derive(Debug, Getters, CopyGetters, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuizResult {
+    #[getset(get = "pub")]
+    questions: Vec<QuestionResult>,
+
+    /// Sum of the scores of all questions.
+    #[getset(get_copy = "pub")]
+    score: f64,
+
+    /// Number of questions, each is worth 1 point.
+    #[getset(get_copy = "pub")]
+    max_score: f64,
+}
+
+impl QuizResult {
+    /// Score as a percentage of the maximum score, 0 for an empty quiz.
+    #[must_use]
+    pub fn percentage(&self) -> f64 {
+        if self.max_score > 0.0 {
+            self.score / self.max_score * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Number of questions with responses.
+    #[must_use]
+    pub fn answered(&self) -> usize {
+        self.questions
+            .iter()
+            .filter(|q| q.response.is_some())
+            .count()
+    }
+
+    /// Number of responses to open questions waiting for [`QuizSession::grade`].
+    #[must_use]
+    pub fn ungraded(&self) -> usize {
+        self.questions.iter().filter(|q| q.score.is_none()).count()
+    }
+}
+
+// Chosen answers are sorted, deduplicated and valid.
+#[allow(clippy::cast_precision_loss)]
+fn score(question: &ClosedQuestion, answers: &[usize], scoring: Scoring) -> f64 {
+    let correct = answers
+        .iter()
+        .filter(|&&idx| question.answers()[idx].is_correct())
+        .count();
+    let incorrect = answers.len() - correct;
+    let expected = question.correct_count();
+    match scoring {
+        Scoring::AllOrNothing => f64::from(u8::from(correct == expected && incorrect == 0)),
+        Scoring::Partial if expected == 0 => f64::from(u8::from(incorrect == 0)),
+        Scoring::Partial => (correct as f64 - incorrect as f64).max(0.0) / expected as f64,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_quiz_session() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi`
+            Which bundle is active?
+
+            ## Answers
+            - [ ] a
+            - [X] b
+            - [ ] c
+
+            ---
+
+            ## Question 2 `OSGi`
+            Which TWO bundles are active?
+
+            ## Answers
+            - [X] a
+            - [X] b
+            - [ ] c
+
+            ---
+
+            ## Question 3 `Services`
+            Describe services.
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+        let mut session = questions.quiz();
+
+        assert_eq!(session.current().map(Question::number), Some(1));
+        assert_eq!(session.respond(vec![1])?, Some(1.0));
+        assert_eq!(session.respond(vec![2, 0])?, Some(0.0));
+        assert_eq!(session.respond("Components with interfaces")?, None);
+        assert!(session.is_finished());
+        assert_eq!(session.respond(vec![0]), Err(QuizError::Finished));
+
+        let result = session.result();
+        assert!((result.score() - 1.0).abs() < f64::EPSILON);
+        assert!((result.max_score() - 3.0).abs() < f64::EPSILON);
+        assert_eq!(result.ungraded(), 1);
+
+        session.grade(2, 0.5)?;
+        let result = session.result();
+        assert!((result.score() - 1.5).abs() < f64::EPSILON);
+        assert!((result.percentage() - 50.0).abs() < f64::EPSILON);
+        assert_eq!(result.ungraded(), 0);
+        assert_eq!(
+            result.questions()[1].response(),
+            Some(&Response::Closed(vec![0, 2]))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_scoring() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi`
+            Which bundle is active?
+
+            ## Answers
+            - [ ] a
+            - [X] b
+            - [ ] c
+
+            ---
+
+            ## Question 2 `OSGi`
+            Which TWO bundles are active?
+
+            ## Answers
+            - [X] a
+            - [X] b
+            - [ ] c
+
+            ---
+
+            ## Question 3 `Services`
+            Describe services.
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+        let mut session = questions.quiz();
+        session.skip();
+        session.respond(vec![0])?;
+        session.go_to(1)?;
+
+        assert_eq!(session.respond(vec![0, 2])?, Some(0.0));
+        let session = session.scoring(Scoring::Partial);
+        assert_eq!(session.score(1), Some(0.0));
+
+        let mut session = questions.quiz().scoring(Scoring::Partial);
+        session.skip();
+        assert_eq!(session.respond(vec![1, 1])?, Some(0.5));
+        assert_eq!(session.position(), 2);
+
+        let result = session.result();
+        assert_eq!(result.answered(), 1);
+        assert_eq!(result.questions()[0].score(), Some(0.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_responses() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi`
+            Which bundle is active?
+
+            ## Answers
+            - [ ] a
+            - [X] b
+            - [ ] c
+
+            ---
+
+            ## Question 2 `OSGi`
+            Which TWO bundles are active?
+
+            ## Answers
+            - [X] a
+            - [X] b
+            - [ ] c
+
+            ---
+
+            ## Question 3 `Services`
+            Describe services.
+
+            ## Answer
+            Answer
+
+            ---
+        "})?;
+        let mut session = questions.quiz();
+
+        assert_eq!(
+            session.respond("Answer"),
+            Err(QuizError::WrongKind { question: 1 })
+        );
+        assert_eq!(
+            session.respond(vec![1, 3]),
+            Err(QuizError::InvalidAnswer {
+                question: 1,
+                answer: 3
+            })
+        );
+        assert_eq!(session.position(), 0);
+        assert_eq!(session.go_to(3), Err(QuizError::OutOfRange { index: 3 }));
+        assert_eq!(
+            session.grade(0, 1.0),
+            Err(QuizError::WrongKind { question: 1 })
+        );
+        assert_eq!(
+            session.grade(2, 1.0),
+            Err(QuizError::NoResponse { question: 3 })
+        );
+        session.go_to(2)?;
+        session.respond("Answer")?;
+        assert_eq!(
+            session.grade(2, f64::NAN),
+            Err(QuizError::InvalidScore { question: 3 })
+        );
+        assert_eq!(session.score(2), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use crate::answer::ClosedAnswer;
+use crate::question::{ClosedQuestion, Question};
+use crate::MdQuestions;
+
+use rand::seq::{index, SliceRandom};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+impl MdQuestions {
+    /// Draws `n` random questions in random order. All questions are returned (shuffled) when
+    /// there are less than `n` of them.
+    ///
+    /// The same `seed` gives the same draw for the same questions, on every platform.
+    #[must_use]
+    pub fn sample(&self, n: usize, seed: u64) -> Vec<&Question> {
+        draw(
+            &mut rng(seed),
+            &self.questions.iter().collect::<Vec<_>>(),
+            n,
+        )
+    }
+
+    /// Draws given numbers of random questions from given categories, e.g.
+    /// `&[("Templates and Components", 3), ("OSGi Services", 2)]`.
+    ///
+    /// Questions are grouped by categories in the order they are given. All questions of a
+    /// category are returned when there are less of them than requested. The same `seed` gives
+    /// the same draw for the same questions.
+    #[must_use]
+    pub fn sample_by_category<S: AsRef<str>>(
+        &self,
+        counts: &[(S, usize)],
+        seed: u64,
+    ) -> Vec<&Question> {
+        let mut rng = rng(seed);
+        counts
+            .iter()
+            .flat_map(|(category, n)| {
+                let questions = self
+                    .questions
+                    .iter()
+                    .filter(|q| q.category() == category.as_ref())
+                    .collect::<Vec<_>>();
+                draw(&mut rng, &questions, *n)
+            })
+            .collect()
+    }
+}
+
+impl ClosedQuestion {
+    /// Answers in random order with the mapping back to their original indices, so the answers
+    /// chosen from the shuffled list can be graded. The same `seed` gives the same order.
+    #[must_use]
+    pub fn shuffled_answers(&self, seed: u64) -> ShuffledAnswers<'_> {
+        let mut original = (0..self.answers.len()).collect::<Vec<_>>();
+        original.shuffle(&mut rng(seed));
+        let answers = original.iter().map(|&idx| &self.answers[idx]).collect();
+        ShuffledAnswers { answers, original }
+    }
+}
+
+/// Shuffled answers of a closed question. See [`ClosedQuestion::shuffled_answers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShuffledAnswers<'a> {
+    answers: Vec<&'a ClosedAnswer>,
+    original: Vec<usize>,
+}
+
+impl<'a> ShuffledAnswers<'a> {
+    /// Answers in the shuffled order.
+    #[must_use]
+    pub fn answers(&self) -> &[&'a ClosedAnswer] {
+        &self.answers
+    }
+
+    /// Original indices of the answers in the shuffled order, i.e. `original_indices()[i]` is the
+    /// index of the `i`-th shuffled answer in [`ClosedQuestion::answers`].
+    #[must_use]
+    pub fn original_indices(&self) -> &[usize] {
+        &self.original
+    }
+
+    /// Original index of the answer at `idx` in the shuffled order.
+    #[must_use]
+    pub fn original_index(&self, idx: usize) -> Option<usize> {
+        self.original.get(idx).copied()
+    }
+}
+
+// ChaCha gives the same numbers on every platform and for every version of `rand_chacha` 0.3, so
+// draws can be reproduced from seeds.
+fn rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+fn draw<'a>(rng: &mut ChaCha8Rng, questions: &[&'a Question], n: usize) -> Vec<&'a Question> {
+    index::sample(rng, questions.len(), n.min(questions.len()))
+        .into_iter()
+        .map(|idx| questions[idx])
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::answer::OpenAnswer;
+    use anyhow::Result;
+
+    #[test]
+    fn test_sampling_is_reproducible() -> Result<()> {
+        let questions = (1..=10)
+            .map(|number| {
+                Question::open()
+                    .number(number)
+                    .text("Text")
+                    .answer(OpenAnswer::new("Answer"))
+                    .category("Category")
+                    .build()
+                    .map(Question::from)
+            })
+            .collect::<Result<MdQuestions, _>>()?;
+        let sample = |n, seed| {
+            questions
+                .sample(n, seed)
+                .into_iter()
+                .map(Question::number)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(sample(4, 7).len(), 4);
+        assert_eq!(sample(4, 7), sample(4, 7));
+        assert_ne!(sample(4, 8), sample(4, 7));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sampling_more_than_available_returns_all_questions() -> Result<()> {
+        let questions = (1..=10)
+            .map(|number| {
+                Question::open()
+                    .number(number)
+                    .text("Text")
+                    .answer(OpenAnswer::new("Answer"))
+                    .category("Category")
+                    .build()
+                    .map(Question::from)
+            })
+            .collect::<Result<MdQuestions, _>>()?;
+
+        let mut sample = questions
+            .sample(20, 1)
+            .into_iter()
+            .map(Question::number)
+            .collect::<Vec<_>>();
+        sample.sort_unstable();
+
+        assert_eq!(sample, (1..=10).collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sampling_by_category() -> Result<()> {
+        let questions = (1..=10)
+            .map(|number| {
+                Question::open()
+                    .number(number)
+                    .text("Text")
+                    .answer(OpenAnswer::new("Answer"))
+                    .category(if number <= 6 { "A" } else { "B" })
+                    .build()
+                    .map(Question::from)
+            })
+            .collect::<Result<MdQuestions, _>>()?;
+
+        let sample = questions.sample_by_category(&[("B", 2), ("A", 3), ("C", 1)], 3);
+
+        let categories = sample.iter().map(|q| q.category()).collect::<Vec<_>>();
+        assert_eq!(categories, ["B", "B", "A", "A", "A"]);
+        assert_eq!(
+            questions.sample_by_category(&[("B", 2), ("A", 3), ("C", 1)], 3),
+            sample
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_shuffled_answers_map_back_to_original_indices() -> Result<()> {
+        let question = Question::closed()
+            .number(1)
+            .text("Text")
+            .answers(
+                (0..6)
+                    .map(|idx| ClosedAnswer::new(idx.to_string(), idx == 2))
+                    .collect::<Vec<_>>(),
+            )
+            .category("Category")
+            .build()?;
+
+        let shuffled = question.shuffled_answers(5);
+
+        assert_eq!(shuffled, question.shuffled_answers(5));
+        assert_ne!(shuffled.original_indices(), [0, 1, 2, 3, 4, 5]);
+        for (idx, answer) in shuffled.answers().iter().enumerate() {
+            let original = shuffled.original_index(idx).unwrap();
+            assert_eq!(*answer, &question.answers()[original]);
+        }
+        assert_eq!(shuffled.original_index(6), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,456 @@
+//! Full-text search over question texts, answers and categories.
+//!
+//! [`SearchIndex`] is an inverted index of the question bank. Queries are ranked with BM25 and can
+//! contain words, phrases in double quotes and prefixes ending with `*`. Each [`Hit`] points to the
+//! question and to the places in its texts which matched:
+//!
+//! ```
+//! use md_questions::search::SearchIndex;
+//! use md_questions::MdQuestions;
+//!
+//! let questions = MdQuestions::parse("")?;
+//! let index = SearchIndex::new(&questions);
+//! for hit in index.search("\"service reference\" osgi bundl*") {
+//!     println!("question {} ({})", hit.number(), hit.score());
+//! }
+//! # Ok::<(), md_questions::ParseError>(())
+//! ```
+
+use crate::question::Question;
+use crate::MdQuestions;
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+// Usual BM25 parameters: saturation of term frequency and normalisation of question length.
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// Part of the question where the text matched.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Field {
+    /// Question text.
+    Text,
+    /// Text of the answer at given index. Open questions have a single answer at index 0.
+    Answer(usize),
+    /// Question category.
+    Category,
+}
+
+/// Place where the query matched: byte range in the text of the field.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Match {
+    field: Field,
+    range: Range<usize>,
+}
+
+impl Match {
+    #[must_use]
+    pub fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Byte range of the matched words in the text of the field, e.g. in
+    /// [`Question::text`](crate::Question::text) for [`Field::Text`].
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+/// Question found by [`SearchIndex::search`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    index: usize,
+    number: i32,
+    score: f64,
+    matches: Vec<Match>,
+}
+
+impl Hit {
+    /// Index of the question in the searched [`MdQuestions`].
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[must_use]
+    pub fn number(&self) -> i32 {
+        self.number
+    }
+
+    /// BM25 score of the question. Better matching questions have higher scores.
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Places where the query matched, ordered by fields and positions.
+    #[must_use]
+    pub fn matches(&self) -> &[Match] {
+        &self.matches
+    }
+}
+
+/// Inverted index of question texts, answers and categories.
+#[derive(Debug, Clone)]
+pub struct SearchIndex {
+    postings: BTreeMap<String, Vec<Posting>>,
+    documents: Vec<Document>,
+    average_length: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Document {
+    number: i32,
+    length: usize,
+}
+
+// Occurrence of a word: in which question, in which field and where.
+#[derive(Debug, Clone)]
+struct Posting {
+    document: usize,
+    field: Field,
+    position: usize,
+    range: Range<usize>,
+}
+
+enum Clause {
+    Word(String),
+    Prefix(String),
+    Phrase(Vec<String>),
+}
+
+impl SearchIndex {
+    // Word counts stay far below the 2^52 where `f64` loses precision.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(questions: &MdQuestions) -> Self {
+        let mut postings = BTreeMap::<String, Vec<Posting>>::new();
+        let mut documents = Vec::new();
+        for (document, question) in questions.iter().enumerate() {
+            let mut length = 0;
+            for (field, text) in fields(question) {
+                for (position, (word, range)) in words(text).enumerate() {
+                    postings.entry(word).or_default().push(Posting {
+                        document,
+                        field,
+                        position,
+                        range,
+                    });
+                    length += 1;
+                }
+            }
+            documents.push(Document {
+                number: question.number(),
+                length,
+            });
+        }
+        let total = documents.iter().map(|d| d.length).sum::<usize>();
+        let average_length = total as f64 / documents.len().max(1) as f64;
+        Self {
+            postings,
+            documents,
+            average_length,
+        }
+    }
+
+    /// Questions matching any word, prefix (`bundl*`) or phrase (`"service reference"`) of the
+    /// query, the best matching first. Matching ignores case.
+    #[must_use]
+    pub fn search(&self, query: &str) -> Vec<Hit> {
+        let mut hits = HashMap::<usize, Hit>::new();
+        for clause in clauses(query) {
+            match clause {
+                Clause::Word(word) => {
+                    if let Some(postings) = self.postings.get(&word) {
+                        self.score(&mut hits, postings.iter().collect());
+                    }
+                }
+                Clause::Prefix(prefix) => {
+                    let words = self
+                        .postings
+                        .range(prefix.clone()..)
+                        .take_while(|(word, _)| word.starts_with(&prefix));
+                    for (_, postings) in words {
+                        self.score(&mut hits, postings.iter().collect());
+                    }
+                }
+                Clause::Phrase(words) => self.score_phrase(&mut hits, &words),
+            }
+        }
+        let mut hits = hits.into_values().collect::<Vec<_>>();
+        for hit in &mut hits {
+            hit.matches
+                .sort_by_key(|m| (m.field, m.range.start, m.range.end));
+            hit.matches.dedup();
+        }
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.index.cmp(&b.index)));
+        hits
+    }
+
+    // Occurrences of a phrase are scored like occurrences of a single word, with the document
+    // frequency of the phrase.
+    fn score_phrase(&self, hits: &mut HashMap<usize, Hit>, words: &[String]) {
+        let Some((first, rest)) = words.split_first() else {
+            return;
+        };
+        let Some(first) = self.postings.get(first) else {
+            return;
+        };
+        let mut following = Vec::new();
+        for word in rest {
+            let Some(postings) = self.postings.get(word) else {
+                return;
+            };
+            following.push(postings);
+        }
+        let positions = following
+            .iter()
+            .map(|postings| {
+                postings
+                    .iter()
+                    .map(|p| ((p.document, p.field, p.position), p.range.end))
+                    .collect::<HashMap<_, _>>()
+            })
+            .collect::<Vec<_>>();
+        let occurrences = first
+            .iter()
+            .filter_map(|p| {
+                let mut end = p.range.end;
+                for (offset, positions) in positions.iter().enumerate() {
+                    end = *positions.get(&(p.document, p.field, p.position + offset + 1))?;
+                }
+                Some(Posting {
+                    range: p.range.start..end,
+                    ..p.clone()
+                })
+            })
+            .collect::<Vec<_>>();
+        self.score(hits, occurrences.iter().collect());
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn score(&self, hits: &mut HashMap<usize, Hit>, postings: Vec<&Posting>) {
+        let mut by_document = BTreeMap::<usize, Vec<&Posting>>::new();
+        for posting in postings {
+            by_document
+                .entry(posting.document)
+                .or_default()
+                .push(posting);
+        }
+        let count = self.documents.len() as f64;
+        let frequency = by_document.len() as f64;
+        let idf = (1.0 + (count - frequency + 0.5) / (frequency + 0.5)).ln();
+        for (document, postings) in by_document {
+            let Document { number, length } = self.documents[document];
+            let tf = postings.len() as f64;
+            let norm = 1.0 - B + B * length as f64 / self.average_length;
+            let hit = hits.entry(document).or_insert_with(|| Hit {
+                index: document,
+                number,
+                score: 0.0,
+                matches: Vec::new(),
+            });
+            hit.score += idf * tf * (K1 + 1.0) / (tf + K1 * norm);
+            hit.matches.extend(postings.iter().map(|p| Match {
+                field: p.field,
+                range: p.range.clone(),
+            }));
+        }
+    }
+}
+
+impl MdQuestions {
+    /// Builds [`SearchIndex`] of the questions.
+    #[must_use]
+    pub fn search_index(&self) -> SearchIndex {
+        SearchIndex::new(self)
+    }
+}
+
+fn fields(question: &Question) -> Vec<(Field, &str)> {
+    let mut fields = vec![(Field::Text, question.text())];
+    match question {
+        Question::Closed(q) => fields.extend(
+            q.answers()
+                .iter()
+                .enumerate()
+                .map(|(idx, answer)| (Field::Answer(idx), answer.text().as_str())),
+        ),
+        Question::Open(q) => fields.push((Field::Answer(0), q.answer().text())),
+    }
+    fields.push((Field::Category, question.category()));
+    fields
+}
+
+// Lowercase words made of letters and digits with their byte ranges in the text.
+fn words(text: &str) -> impl Iterator<Item = (String, Range<usize>)> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(move |word| {
+            let start = word.as_ptr() as usize - text.as_ptr() as usize;
+            (word.to_lowercase(), start..start + word.len())
+        })
+}
+
+fn clauses(query: &str) -> Vec<Clause> {
+    let mut clauses = Vec::new();
+    for (idx, part) in query.split('"').enumerate() {
+        // Odd parts are between double quotes.
+        if idx % 2 == 1 {
+            let words = words(part).map(|(word, _)| word).collect::<Vec<_>>();
+            if !words.is_empty() {
+                clauses.push(Clause::Phrase(words));
+            }
+            continue;
+        }
+        for term in part.split_whitespace() {
+            let prefix = term.strip_suffix('*');
+            let words = words(prefix.unwrap_or(term)).map(|(word, _)| word);
+            match prefix {
+                Some(_) => clauses.extend(words.map(Clause::Prefix)),
+                None => clauses.extend(words.map(Clause::Word)),
+            }
+        }
+    }
+    clauses
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_searching_ranks_questions() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi Services`
+            How to get a service reference of an OSGi service?
+
+            ## Answers
+            - [X] Use BundleContext
+            - [ ] Use a reference to the service
+
+            ---
+
+            ## Question 2 `OSGi Bundles`
+            Which bundles are active?
+
+            ## Answers
+            - [X] All of them
+
+            ---
+
+            ## Question 3 `Sling`
+            Describe the Sling resource resolution.
+
+            ## Answer
+            Resources are resolved by the path.
+
+            ---
+        "})?;
+
+        let hits = questions.search_index().search("OSGi service");
+
+        let numbers = hits.iter().map(Hit::number).collect::<Vec<_>>();
+        assert_eq!(numbers, [1, 2]);
+        assert!(hits[0].score() > hits[1].score());
+        assert_eq!(
+            hits[1].matches(),
+            [Match {
+                field: Field::Category,
+                range: 0..4
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_searching_phrases() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi Services`
+            How to get a service reference of an OSGi service?
+
+            ## Answers
+            - [X] Use BundleContext
+            - [ ] Use a reference to the service
+
+            ---
+        "})?;
+        let index = questions.search_index();
+
+        let hits = index.search("\"SERVICE reference\"");
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            hits[0].matches(),
+            [Match {
+                field: Field::Text,
+                range: 13..30
+            }]
+        );
+        assert!(index.search("\"reference service\"").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_searching_prefixes() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi Bundles`
+            Which bundles are active?
+
+            ## Answers
+            - [X] All of them
+
+            ---
+
+            ## Question 2 `Sling`
+            Describe the Sling resource resolution.
+
+            ## Answer
+            Resources are resolved by the path.
+
+            ---
+        "})?;
+
+        let hits = questions.search_index().search("resol*");
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].number(), 2);
+        let [text, answer] = hits[0].matches() else {
+            panic!("expected two matches");
+        };
+        assert_eq!(text.field(), Field::Text);
+        assert_eq!(&questions[1].text()[text.range()], "resolution");
+        assert_eq!(answer.field(), Field::Answer(0));
+        let answer_text = questions[1].as_open().unwrap().answer().text();
+        assert_eq!(&answer_text[answer.range()], "resolved");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_searching_without_matches() -> Result<()> {
+        let questions = MdQuestions::parse(indoc! {"
+            ## Question 1 `OSGi Services`
+            How to get a service reference of an OSGi service?
+
+            ## Answers
+            - [X] Use BundleContext
+
+            ---
+        "})?;
+        let index = questions.search_index();
+
+        assert!(index.search("").is_empty());
+        assert!(index.search("AEM").is_empty());
+        assert!(index.search("\"OSGi AEM\"").is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use getset::CopyGetters;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Place in the parsed markdown.
+///
+/// `offset` is counted in bytes from the beginning of the input, `line` and `column` are 1-based
+/// and columns are counted in characters.
+#[derive(Default, Debug, CopyGetters, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[getset(get_copy = "pub")]
+pub struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    #[must_use]
+    pub fn new(offset: usize, line: usize, column: usize) -> Self {
+        Self {
+            offset,
+            line,
+            column,
+        }
+    }
+}
+
+/// Range of the parsed markdown. `end` points right after the last character of the range.
+#[derive(Default, Debug, CopyGetters, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[getset(get_copy = "pub")]
+pub struct Span {
+    start: Position,
+    end: Position,
+}
+
+impl Span {
+    #[must_use]
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// Byte range which can be used to slice the parsed input.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
+    }
+}
+
+/// Spans of the parts of a parsed question.
+#[derive(Default, Debug, CopyGetters, Clone, Copy, Eq, PartialEq)]
+#[getset(get_copy = "pub")]
+pub struct QuestionSpans {
+    /// From the question header up to the end of the questions separator.
+    question: Span,
+
+    /// ``## Question <number> `<category>` ``
+    header: Span,
+
+    /// Question text.
+    text: Span,
+
+    /// `## Hints` section.
+    hints: Option<Span>,
+
+    /// All closed answers or the text of the open answer.
+    answers: Span,
+
+    /// `## Explanation` section of the closed question.
+    explanation: Option<Span>,
+
+    /// `## [Reading](<url>)`
+    reading: Option<Span>,
+}
+
+impl QuestionSpans {
+    pub(crate) fn new(
+        question: Span,
+        header: Span,
+        text: Span,
+        hints: Option<Span>,
+        answers: Span,
+        explanation: Option<Span>,
+        reading: Option<Span>,
+    ) -> Self {
+        Self {
+            question,
+            header,
+            text,
+            hints,
+            answers,
+            explanation,
+            reading,
+        }
+    }
+}
+
+/// Source location attached to parsed values.
+///
+/// Location is not a part of the value itself, so it's ignored when values are compared. This way
+/// parsed questions are equal to the ones created with builders.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct Location<T>(Option<T>);
+
+impl<T> Location<T> {
+    pub(crate) fn new(location: T) -> Self {
+        Self(Some(location))
+    }
+
+    pub(crate) fn get(&self) -> Option<&T> {
+        self.0.as_ref()
+    }
+}
+
+impl<T> PartialEq for Location<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Location<T> {}
@@ -0,0 +1,370 @@
+use crate::answer::{ClosedAnswer, OpenAnswer};
+use crate::ignored::IgnoredQuestion;
+use crate::marker::Marker;
+use crate::question::{ClosedQuestion, OpenQuestion, Question};
+use crate::MdQuestions;
+
+use std::fmt::{self, Display};
+
+// Continuation lines of multi-line answers are aligned with the text after the checkbox.
+const CONTINUATION_INDENT: &str = "      ";
+
+impl MdQuestions {
+    /// Renders questions back to markdown following the convention described in README.
+    ///
+    /// Parsing the rendered markdown gives back the same questions, as long as the questions can
+    /// be written using the convention (e.g. closed answers and hints don't contain empty lines,
+    /// numbers are not negative and closed questions have at least one answer).
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        self.to_string()
+    }
+}
+
+// Ignored questions are rendered in the place they were parsed from.
+impl Display for MdQuestions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ignored = self.ignored.iter().peekable();
+        let mut blocks = Vec::<&dyn Display>::new();
+        for (idx, question) in self.questions.iter().enumerate() {
+            while let Some(ignored) = ignored.next_if(|ignored| ignored.position <= idx) {
+                blocks.push(ignored);
+            }
+            blocks.push(question);
+        }
+        blocks.extend(ignored.map(|ignored| ignored as &dyn Display));
+        for (idx, block) in blocks.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{block}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for IgnoredQuestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.raw)
+    }
+}
+
+impl Display for Question {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed(q) => write!(f, "{q}"),
+            Self::Open(q) => write!(f, "{q}"),
+        }
+    }
+}
+
+// Example of rendered closed question:
+//
+// ```markdown
+// ## Question 3 `Category 3`
+// Question 3 text
+//
+// ## Hints
+// 1. Hint 1
+//
+// ## Answers
+// - [ ] Answer 1
+//       > Explanation of answer 1
+// - [X] Answer 2
+//
+// ## Explanation
+// Explanation of question 3
+//
+// ## [Reading](Reading 3)
+//
+// ---
+// ```
+impl Display for ClosedQuestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_prolog(
+            f,
+            *self.number(),
+            self.category(),
+            self.markers(),
+            self.tags(),
+            self.text(),
+            self.hints(),
+        )?;
+        writeln!(f, "## Answers")?;
+        for answer in self.answers() {
+            writeln!(f, "{answer}")?;
+        }
+        if let Some(explanation) = self.explanation() {
+            writeln!(f)?;
+            writeln!(f, "## Explanation")?;
+            writeln!(f, "{explanation}")?;
+        }
+        write_epilog(f, self.reading().as_deref())
+    }
+}
+
+impl Display for OpenQuestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_prolog(
+            f,
+            self.number,
+            &self.category,
+            &self.markers,
+            &self.tags,
+            &self.text,
+            &self.hints,
+        )?;
+        writeln!(f, "## Answer")?;
+        writeln!(f, "{}", self.answer)?;
+        write_epilog(f, self.reading.as_deref())
+    }
+}
+
+impl Display for ClosedAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let checkbox = if self.is_correct() { "- [X]" } else { "- [ ]" };
+        let mut lines = self.text().lines();
+        write!(f, "{checkbox} {}", lines.next().unwrap_or_default())?;
+        for line in lines {
+            write!(f, "\n{CONTINUATION_INDENT}{line}")?;
+        }
+        for line in self.explanation().iter().flat_map(|text| text.split('\n')) {
+            let quoted = format!("> {line}");
+            write!(f, "\n{CONTINUATION_INDENT}{}", quoted.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for OpenAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+fn write_prolog(
+    f: &mut fmt::Formatter<'_>,
+    number: i32,
+    category: &str,
+    markers: &[Marker],
+    tags: &[String],
+    text: &str,
+    hints: &[String],
+) -> fmt::Result {
+    write!(f, "## Question {number} `{category}`")?;
+    for marker in markers {
+        write!(f, " `{marker}`")?;
+    }
+    writeln!(f)?;
+    if !tags.is_empty() {
+        write!(f, "Tags:")?;
+        for tag in tags {
+            write!(f, " `{tag}`")?;
+        }
+        writeln!(f)?;
+    }
+    writeln!(f, "{text}")?;
+    writeln!(f)?;
+    if !hints.is_empty() {
+        writeln!(f, "## Hints")?;
+        for (idx, hint) in hints.iter().enumerate() {
+            let marker = format!("{}. ", idx + 1);
+            let mut lines = hint.lines();
+            writeln!(f, "{marker}{}", lines.next().unwrap_or_default())?;
+            for line in lines {
+                writeln!(f, "{:width$}{line}", "", width = marker.len())?;
+            }
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+fn write_epilog(f: &mut fmt::Formatter<'_>, reading: Option<&str>) -> fmt::Result {
+    writeln!(f)?;
+    if let Some(reading) = reading {
+        writeln!(f, "## [Reading]({reading})")?;
+        writeln!(f)?;
+    }
+    writeln!(f, "---")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::Result;
+    use indoc::indoc;
+
+    #[test]
+    fn test_rendering_closed_question() -> Result<()> {
+        let question: Question = Question::closed()
+            .number(3)
+            .text("Question 3 text\nsecond line")
+            .answers(vec![
+                ClosedAnswer::incorrect("Answer 1").with_explanation("Because\n\nof reasons"),
+                ClosedAnswer::correct("Answer 2\nwrapped"),
+            ])
+            .explanation(Some("Explanation of question 3".into()))
+            .reading(Some("Reading 3".into()))
+            .category("Category 3")
+            .build()?
+            .into();
+
+        assert_eq!(
+            question.to_string(),
+            indoc! {"
+                ## Question 3 `Category 3`
+                Question 3 text
+                second line
+
+                ## Answers
+                - [ ] Answer 1
+                      > Because
+                      >
+                      > of reasons
+                - [X] Answer 2
+                      wrapped
+
+                ## Explanation
+                Explanation of question 3
+
+                ## [Reading](Reading 3)
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rendering_question_markers_tags_and_hints() -> Result<()> {
+        let question: Question = Question::open()
+            .number(1)
+            .text("Question 1 text")
+            .answer(OpenAnswer::new("Answer"))
+            .category("Category 1")
+            .markers(vec![Marker::Draft, Marker::Custom("Needs Image".into())])
+            .tags(vec!["Sling".into(), "exam-2023".into()])
+            .hints(vec!["Hint 1".into(), "Hint 2\nwrapped".into()])
+            .build()?
+            .into();
+
+        assert_eq!(
+            question.to_string(),
+            indoc! {"
+                ## Question 1 `Category 1` `Draft` `Needs Image`
+                Tags: `Sling` `exam-2023`
+                Question 1 text
+
+                ## Hints
+                1. Hint 1
+                2. Hint 2
+                   wrapped
+
+                ## Answer
+                Answer
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rendering_open_question() -> Result<()> {
+        let question: Question = Question::open()
+            .number(1)
+            .text("Question 1 text")
+            .answer(OpenAnswer::new("Answer"))
+            .category("Category 1")
+            .build()?
+            .into();
+
+        assert_eq!(
+            question.to_string(),
+            indoc! {"
+                ## Question 1 `Category 1`
+                Question 1 text
+
+                ## Answer
+                Answer
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rendering_questions_separates_them_with_empty_line() -> Result<()> {
+        let question: Question = Question::open()
+            .number(1)
+            .text("Text")
+            .answer(OpenAnswer::new("Answer"))
+            .category("Category")
+            .build()?
+            .into();
+        let questions = MdQuestions::new(vec![question.clone(), question]);
+
+        assert_eq!(
+            questions.to_markdown(),
+            indoc! {"
+                ## Question 1 `Category`
+                Text
+
+                ## Answer
+                Answer
+
+                ---
+
+                ## Question 1 `Category`
+                Text
+
+                ## Answer
+                Answer
+
+                ---
+            "}
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rendering_ignored_questions_in_place() -> Result<()> {
+        let content = indoc! {"
+            ## Question 1 `Category` `Ignore`
+            Unfinished
+
+            ---
+
+            ## Question 2 `Category`
+            Text
+
+            ## Answer
+            Answer
+
+            ---
+
+            ## Question 3 `Category` `Ignore`
+            Unfinished
+
+            ---
+        "};
+
+        let questions = MdQuestions::parse(content)?;
+
+        assert_eq!(questions.to_markdown(), content);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rendering_empty_questions() {
+        assert_eq!(MdQuestions::default().to_markdown(), "");
+    }
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use md_questions::{format, format_with, is_formatted, FormatOptions, MdQuestions, Wrap};
+use std::fs::read_to_string;
+
+#[test]
+fn test_formatting_keeps_questions_unchanged() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+
+    let formatted = format(&content)?;
+
+    assert_eq!(
+        MdQuestions::parse(&formatted)?,
+        MdQuestions::parse(&content)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_formatting_is_idempotent() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let options = FormatOptions::default().wrap(Wrap::At(80));
+
+    let formatted = format_with(&content, &options)?;
+
+    assert_eq!(format_with(&formatted, &options)?, formatted);
+
+    Ok(())
+}
+
+#[test]
+fn test_formatted_file_is_reported_as_formatted() -> Result<()> {
+    let content = read_to_string("res/open-questions.md")?;
+
+    assert!(is_formatted(&content)?);
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "html")]
+
+use anyhow::Result;
+use indoc::indoc;
+use md_questions::{ClosedAnswer, MdQuestions, OpenAnswer, Question};
+use std::fs::read_to_string;
+
+#[test]
+fn test_rendering_question_text_to_html() -> Result<()> {
+    let question = Question::closed()
+        .number(1)
+        .text("Which keyword declares a binding?\n\n```rust\nlet x = 1;\n```")
+        .answers(vec![ClosedAnswer::correct("`let`")])
+        .category("Rust")
+        .build()?;
+
+    assert_eq!(
+        question.text_html(),
+        indoc! {r#"
+            <p>Which keyword declares a binding?</p>
+            <pre><code class="language-rust">let x = 1;
+            </code></pre>
+        "#}
+    );
+    assert_eq!(
+        question.answers()[0].text_html(),
+        "<p><code>let</code></p>\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rendering_escapes_raw_html_and_unsafe_links() {
+    let answer = OpenAnswer::new(
+        "<script>alert(1)</script>\n\nSee [docs](javascript:alert(1)) & <b>this</b>",
+    );
+
+    assert_eq!(
+        answer.html(),
+        indoc! {r#"
+            &lt;script&gt;alert(1)&lt;/script&gt;
+            <p>See <a href="">docs</a> &amp; &lt;b&gt;this&lt;/b&gt;</p>
+        "#}
+    );
+}
+
+#[test]
+fn test_exporting_questions_to_html_page() -> Result<()> {
+    let questions = MdQuestions::parse(&read_to_string("res/closed-questions.md")?)?;
+
+    let html = questions.to_html();
+
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.ends_with("</body>\n</html>\n"));
+    assert_eq!(
+        html.matches("<section class=\"question\"").count(),
+        questions.count()
+    );
+    assert_eq!(
+        html.matches("<details class=\"answers\">").count(),
+        questions.count()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_exporting_questions_with_obfuscated_unsafe_reading_links() -> Result<()> {
+    let questions = MdQuestions::parse(
+        "## Question 1 `Category`\nText\n\n## Answer\nAnswer\n\n\
+         ## [Reading](java\tscript:alert`3`)\n\n---\n\n\
+         ## Question 2 `Category`\nText\n\n## Answer\nAnswer\n\n\
+         ## [Reading](\u{1}javascript:alert`4`)\n\n---\n",
+    )?;
+
+    let html = questions.to_html();
+
+    assert_eq!(questions[0].reading(), Some("java\tscript:alert`3`"));
+    assert_eq!(questions[1].reading(), Some("\u{1}javascript:alert`4`"));
+    assert!(!html.contains("script:"));
+    assert_eq!(html.matches("<a href=\"\">Reading</a>").count(), 2);
+
+    Ok(())
+}
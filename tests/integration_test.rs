@@ -1,11 +1,15 @@
+use anyhow::Result;
 use indoc::indoc;
-use md_questions::{ClosedAnswer, MdQuestions, OpenAnswer};
+use md_questions::{
+    ClosedAnswer, Element, LineBreaks, Marker, MdQuestions, OpenAnswer, ParseError, ParseOptions,
+    Question, Severity,
+};
 use std::fs::read_to_string;
 
 #[test]
-fn test_reading_closed_questions_from_file() {
-    let content = read_to_string("res/closed-questions.md").unwrap();
-    let questions = MdQuestions::from(content.as_str());
+fn test_reading_closed_questions_from_file() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let questions = MdQuestions::parse(&content)?;
     let first_question = &questions[0];
 
     let Some(closed_question) = first_question.as_closed() else {
@@ -33,12 +37,163 @@ fn test_reading_closed_questions_from_file() {
         ]
     );
     assert!(closed_question.reading().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_reading_multi_line_answers_from_file() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let options = ParseOptions::default().line_breaks(LineBreaks::Join);
+    let (questions, _) = MdQuestions::parse_recovering_with(&content, &options);
+
+    let Some(closed_question) = questions[1].as_closed() else {
+        panic!("Should not happen");
+    };
+    assert_eq!(*closed_question.number(), 2);
+    assert_eq!(
+        closed_question.answers(),
+        &[
+            ClosedAnswer::correct(
+                "The bundles consuming the service need to import the fully qualified name of \
+                the service interface."
+            ),
+            ClosedAnswer::incorrect("The service needs to correctly declare metatype information."),
+            ClosedAnswer::incorrect(
+                "The bundle providing the service needs to contain a whitelist of allowed \
+                consumer bundles."
+            ),
+            ClosedAnswer::incorrect(
+                "The bundle providing the service needs to contain an adequate SCR descriptor \
+                file."
+            ),
+            ClosedAnswer::correct(
+                "The bundle providing the service needs to export the java package of the \
+                service interface."
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_reading_all_closed_questions_from_file() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+
+    let (questions, diagnostics) = MdQuestions::parse_recovering(&content);
+
+    assert_eq!(questions.count(), 59);
+    assert!(diagnostics.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_reading_expected_choices_of_questions_from_file() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let questions = MdQuestions::parse(&content)?;
+
+    let multi_choice = questions[1].as_closed().expect("closed question");
+    let single_choice = questions[0].as_closed().expect("closed question");
+
+    assert_eq!(multi_choice.expected_choices(), Some(2));
+    assert_eq!(multi_choice.correct_count(), 2);
+    assert_eq!(single_choice.expected_choices(), None);
+    assert_eq!(single_choice.correct_count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_reading_closed_questions_with_windows_line_endings() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let windows_content = content.replace('\n', "\r\n");
+
+    assert_eq!(
+        MdQuestions::parse(&windows_content)?,
+        MdQuestions::parse(&content)?
+    );
+
+    Ok(())
 }
 
 #[test]
-fn test_reading_open_questions_from_file() {
-    let content = read_to_string("res/open-questions.md").unwrap();
-    let questions = MdQuestions::from(content.as_str());
+fn test_parse_error_when_reading_broken_question() {
+    let content = indoc! {"
+        ## Question 1 `Category 1`
+        Question 1 text
+
+        ## Answers
+        - [ ] Answer 1
+        - [X] Answer 2
+
+        ---
+
+        ## Question 2 `Category 2`
+        Question 2 text
+
+        ## Answers
+        - [ ] Answer 1
+        -[X] Answer 2
+
+        ---
+    "};
+
+    let result = MdQuestions::parse(content);
+
+    assert_eq!(
+        result,
+        Err(ParseError::Syntax {
+            line: 15,
+            column: 1,
+            question: Some(2),
+            element: Element::Checkbox,
+        })
+    );
+}
+
+#[test]
+fn test_recovering_from_errors_when_reading_broken_question() {
+    let content = indoc! {"
+        ## Question 1 `Category 1`
+        Question 1 text
+
+        ## Answers
+        -[ ] Answer 1
+        - [X] Answer 2
+
+        ---
+
+        ## Question 2 `Category 2`
+        Question 2 text
+
+        ## Answers
+        - [ ] Answer 1
+        - [X] Answer 2
+
+        ---
+    "};
+
+    let (questions, diagnostics) = MdQuestions::parse_recovering(content);
+
+    assert_eq!(questions.count(), 1);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity(), Severity::Error);
+    assert_eq!(
+        diagnostics[0].span().map(|span| span.start().line()),
+        Some(1)
+    );
+    assert_eq!(
+        diagnostics[0].message(),
+        "invalid answer checkbox of question 1 at 5:2"
+    );
+}
+
+#[test]
+fn test_reading_open_questions_from_file() -> Result<()> {
+    let content = read_to_string("res/open-questions.md")?;
+    let questions: MdQuestions = content.parse()?;
     let first_question = &questions[0];
 
     let Some(open_question) = first_question.as_open() else {
@@ -61,4 +216,202 @@ fn test_reading_open_questions_from_file() {
         })
     );
     assert!(open_question.reading().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_reading_items_of_open_answers_from_file() -> Result<()> {
+    let questions = MdQuestions::parse(&read_to_string("res/open-questions.md")?)?;
+
+    let Some(open_question) = questions[0].as_open() else {
+        panic!("Should not happen");
+    };
+    let items = open_question.answer().items();
+    assert_eq!(items.len(), 8);
+    assert_eq!(items[0], "Undirected Graph");
+    assert_eq!(items[7], "Complete Graph");
+    assert!(open_question.answer().key_points().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_reading_key_points_of_open_answer() {
+    let answer = OpenAnswer::new(indoc! {"
+        Common types:
+
+        - [key] Undirected Graph
+        - Weighted Graph
+        - [Key] Directed Graph"
+    });
+
+    assert_eq!(answer.text(), answer.markdown().raw());
+    assert_eq!(
+        answer.items(),
+        ["Undirected Graph", "Weighted Graph", "Directed Graph"]
+    );
+    assert_eq!(answer.key_points(), ["Undirected Graph", "Directed Graph"]);
+}
+
+#[test]
+fn test_matching_on_question_kinds() -> Result<()> {
+    let content = read_to_string("res/open-questions.md")?;
+    let questions = MdQuestions::parse(&content)?;
+
+    let question = &questions[1];
+    assert!(question.is_open());
+    assert!(!question.is_closed());
+    assert!(question.as_closed().is_none());
+    assert_eq!(question.number(), 2);
+    assert_eq!(question.text(), "Describe Undirected Graph");
+    assert_eq!(question.category(), "Introduction");
+    assert_eq!(question.reading(), None);
+    match question {
+        Question::Open(open_question) => assert_eq!(
+            open_question.answer(),
+            &OpenAnswer::new(
+                "Graph in which edges have no orientation. Edge (u, v) is identical to (v, u)."
+            )
+        ),
+        _ => panic!("Should not happen"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_converting_questions_into_closed_questions() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let questions = MdQuestions::parse(&content)?;
+    let question = questions[2].clone();
+
+    assert!(question.is_closed());
+    assert_eq!(question.reading(), Some("reading/question-3.md"));
+    assert!(question.clone().into_open().is_none());
+    let Some(closed_question) = question.into_closed() else {
+        panic!("Should not happen");
+    };
+    assert_eq!(*closed_question.number(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_reading_ignored_questions() -> Result<()> {
+    let content = indoc! {"
+        ## Question 1 `Category 1` `Ignore` `Hard`
+        Question 1 text
+
+        ## Answers
+        - [ ] Answer 1
+        - [x] Answer 2
+
+        ---
+
+        ## Question 2 `Category 2` `Draft`
+        Unfinished question
+
+        ---
+    "};
+    let options = ParseOptions::default().skip_markers([Marker::Ignore, Marker::Draft]);
+
+    let questions = MdQuestions::parse_with(content, &options)?;
+
+    assert!(questions.is_empty());
+    let ignored = questions.ignored();
+    assert_eq!(ignored.len(), 2);
+    assert_eq!(ignored[1].number(), 2);
+    assert_eq!(ignored[1].category(), "Category 2");
+    assert_eq!(ignored[1].markers(), &vec![Marker::Draft]);
+    assert_eq!(
+        ignored[1].raw(),
+        "## Question 2 `Category 2` `Draft`\nUnfinished question\n\n---"
+    );
+    assert_eq!(
+        ignored[0].parse()?.markers(),
+        [Marker::Ignore, Marker::Hard]
+    );
+    assert_eq!(
+        ignored[1].parse(),
+        Err(ParseError::Syntax {
+            line: 4,
+            column: 1,
+            question: Some(2),
+            element: Element::AnswersHeader
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_filtering_questions_by_tags() -> Result<()> {
+    let content = indoc! {"
+        ## Question 1 `Templates and Components`
+        Tags: `Sling` `exam-2023`
+        Question 1 text
+
+        ## Answer
+        Answer 1
+
+        ---
+
+        ## Question 2 `Templates and Components`
+        Tags: `exam-2023`
+        Question 2 text
+
+        ## Answer
+        Answer 2
+
+        ---
+
+        ## Question 3 `OSGi Services`
+        Question 3 text
+
+        ## Answer
+        Answer 3
+
+        ---
+    "};
+
+    let questions = MdQuestions::parse(content)?;
+
+    assert_eq!(questions[0].tags(), ["Sling", "exam-2023"]);
+    let numbers =
+        |questions: Vec<&Question>| questions.iter().map(|q| q.number()).collect::<Vec<_>>();
+    assert_eq!(
+        numbers(questions.with_any_tag(&["Sling", "exam-2023"]).collect()),
+        vec![1, 2]
+    );
+    assert_eq!(
+        numbers(questions.with_all_tags(&["Sling", "exam-2023"]).collect()),
+        vec![1]
+    );
+    assert_eq!(questions.with_any_tag(&["AEM"]).count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_iterating_over_questions_from_file() -> Result<()> {
+    let questions = MdQuestions::parse(&read_to_string("res/closed-questions.md")?)?;
+
+    assert_eq!(questions.iter().count(), questions.count());
+    assert_eq!((&questions).into_iter().count(), questions.count());
+    assert_eq!(questions.get(0), Some(&questions[0]));
+    assert_eq!(questions.get(questions.count()), None);
+    assert_eq!(questions.by_number(18).map(Question::number), Some(18));
+    assert_eq!(questions.by_number(1000), None);
+    assert_eq!(questions.closed().count(), questions.count());
+    assert_eq!(questions.open().count(), 0);
+    let categories = questions.categories();
+    assert_eq!(categories.values().sum::<usize>(), questions.count());
+    assert_eq!(
+        questions.by_category("OSGi Services").count(),
+        categories["OSGi Services"]
+    );
+    assert_eq!(questions.into_iter().count(), 59);
+
+    Ok(())
 }
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use md_questions::lint::Linter;
+use md_questions::{MdQuestions, Severity};
+use std::fs::read_to_string;
+
+#[test]
+fn test_linting_questions_from_file() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let questions = MdQuestions::parse(&content)?;
+
+    let report = questions.lint();
+
+    assert_eq!(
+        report.to_string(),
+        "364:1: error[duplicate-number]: question number 21 is already used\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_linting_with_lowered_severity() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let questions = MdQuestions::parse(&content)?;
+    let linter = Linter::default().severity("duplicate-number", Severity::Warning);
+
+    let report = linter.lint(&questions);
+
+    assert!(!report.has_errors());
+    assert_eq!(report.count(Severity::Warning), 1);
+
+    Ok(())
+}
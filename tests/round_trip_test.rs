@@ -0,0 +1,193 @@
+use anyhow::Result;
+use md_questions::{
+    format_with, ClosedAnswer, FormatOptions, Marker, MdQuestions, OpenAnswer, Question, Wrap,
+};
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use std::fs::read_to_string;
+
+fn word() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9][A-Za-z0-9.,:;?!'()`-]{0,10}"
+}
+
+// Lines start with a plain word, but can contain words which mean something at the start of a
+// line, so wrapping mustn't move them there.
+fn line() -> impl Strategy<Value = String> {
+    let block = prop_oneof![
+        Just("#"),
+        Just("##"),
+        Just(">"),
+        Just("-"),
+        Just("[X]"),
+        Just("*"),
+        Just("+"),
+        Just("1."),
+        Just("2)"),
+        Just("---"),
+        Just("```"),
+        Just("|"),
+    ];
+    let next = prop_oneof![4 => word(), 1 => block.prop_map(String::from)];
+    (word(), vec(next, 0..7)).prop_map(|(first, words)| {
+        std::iter::once(first)
+            .chain(words)
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
+
+fn lines(max: usize) -> impl Strategy<Value = String> {
+    vec(line(), 1..=max).prop_map(|lines| lines.join("\n"))
+}
+
+// Paragraphs and fenced code blocks separated by blank lines. Code can contain blank lines and
+// lines looking like section headers.
+fn markdown() -> impl Strategy<Value = String> {
+    let code_line = prop_oneof![line(), Just(String::new()), Just("## Answers".to_string())];
+    let code_block = ("(rust|sh|)", vec(code_line, 1..4))
+        .prop_map(|(language, code)| format!("```{language}\n{}\n```", code.join("\n")));
+    vec(prop_oneof![lines(3), code_block], 1..4).prop_map(|blocks| blocks.join("\n\n"))
+}
+
+fn closed_answer() -> impl Strategy<Value = ClosedAnswer> {
+    (lines(3), any::<bool>(), option::of(explanation())).prop_map(
+        |(text, is_correct, explanation)| {
+            let answer = ClosedAnswer::new(text, is_correct);
+            match explanation {
+                Some(explanation) => answer.with_explanation(explanation),
+                None => answer,
+            }
+        },
+    )
+}
+
+// Explanations of answers can have empty lines.
+fn explanation() -> impl Strategy<Value = String> {
+    vec(prop_oneof![line(), Just(String::new())], 1..4)
+        .prop_map(|lines| lines.join("\n"))
+        .prop_filter("not empty", |text| !text.trim().is_empty())
+}
+
+// Questions marked with `Ignore` are skipped by the parser, so they can't be read back.
+fn marker() -> impl Strategy<Value = Marker> {
+    prop_oneof![
+        Just(Marker::Draft),
+        Just(Marker::Review),
+        Just(Marker::Hard),
+        Just(Marker::Deprecated),
+        "[A-Za-z0-9 -]{0,12}"
+            .prop_map(Marker::from)
+            .prop_filter("not skipped", |marker| *marker != Marker::Ignore),
+    ]
+}
+
+fn closed_question() -> impl Strategy<Value = Question> {
+    (
+        0..=i32::MAX,
+        markdown(),
+        (vec(closed_answer(), 1..6), option::of(markdown())),
+        option::of("[a-z0-9/._:-]{0,30}"),
+        "[A-Za-z0-9 ]{0,20}",
+        vec(marker(), 0..3),
+        (vec("[A-Za-z0-9 -]{0,12}", 0..3), vec(lines(2), 0..3)),
+    )
+        .prop_map(
+            |(number, text, (answers, explanation), reading, category, markers, (tags, hints))| {
+                Question::closed()
+                    .number(number)
+                    .text(text)
+                    .answers(answers)
+                    .explanation(explanation)
+                    .reading(reading)
+                    .category(category)
+                    .markers(markers)
+                    .tags(tags)
+                    .hints(hints)
+                    .build()
+                    .unwrap()
+                    .into()
+            },
+        )
+}
+
+fn open_question() -> impl Strategy<Value = Question> {
+    (
+        0..=i32::MAX,
+        markdown(),
+        markdown(),
+        option::of("[a-z0-9/._:-]{0,30}"),
+        "[A-Za-z0-9 ]{0,20}",
+        vec(marker(), 0..3),
+        (vec("[A-Za-z0-9 -]{0,12}", 0..3), vec(lines(2), 0..3)),
+    )
+        .prop_map(
+            |(number, text, answer, reading, category, markers, (tags, hints))| {
+                let mut question = Question::open();
+                question
+                    .number(number)
+                    .text(text)
+                    .answer(OpenAnswer::new(answer))
+                    .category(category)
+                    .markers(markers)
+                    .tags(tags)
+                    .hints(hints);
+                if let Some(reading) = reading {
+                    question.reading(reading);
+                }
+                question.build().unwrap().into()
+            },
+        )
+}
+
+fn questions() -> impl Strategy<Value = MdQuestions> {
+    vec(prop_oneof![closed_question(), open_question()], 0..8).prop_map(MdQuestions::from)
+}
+
+proptest! {
+    #[test]
+    fn test_parsing_rendered_questions_gives_the_same_questions(questions in questions()) {
+        let markdown = questions.to_markdown();
+
+        prop_assert_eq!(MdQuestions::parse(&markdown), Ok(questions));
+    }
+
+    #[test]
+    fn test_parsing_wrapped_questions_gives_the_same_questions(
+        questions in questions(),
+        width in 1..60usize,
+    ) {
+        let markdown = questions.to_markdown();
+        let unwrapped = FormatOptions::default().wrap(Wrap::Never);
+
+        let wrapped = format_with(&markdown, &FormatOptions::default().wrap(Wrap::At(width)));
+
+        // Wrapping only moves line breaks, so unwrapping gives the same questions.
+        let wrapped = wrapped.and_then(|wrapped| format_with(&wrapped, &unwrapped));
+        prop_assert_eq!(wrapped, format_with(&markdown, &unwrapped));
+    }
+}
+
+#[test]
+fn test_rendering_questions_from_files() -> Result<()> {
+    for path in ["res/closed-questions.md", "res/open-questions.md"] {
+        let questions = MdQuestions::parse(&read_to_string(path)?)?;
+
+        let markdown = questions.to_markdown();
+
+        assert_eq!(MdQuestions::parse(&markdown)?, questions);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rendering_keeps_formatted_file_unchanged() -> Result<()> {
+    let content = read_to_string("res/open-questions.md")?;
+
+    let questions = MdQuestions::parse(&content)?;
+
+    assert_eq!(questions.to_string(), content);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use md_questions::search::{Field, SearchIndex};
+use md_questions::MdQuestions;
+use std::fs::read_to_string;
+
+#[test]
+fn test_searching_questions_from_file() -> Result<()> {
+    let questions = MdQuestions::parse(&read_to_string("res/closed-questions.md")?)?;
+    let index = SearchIndex::new(&questions);
+
+    let hits = index.search("\"service component\"");
+
+    assert!(!hits.is_empty());
+    for hit in &hits {
+        let question = &questions[hit.index()];
+        assert_eq!(question.number(), hit.number());
+        for found in hit.matches() {
+            let text = match found.field() {
+                Field::Text => question.text(),
+                Field::Answer(idx) => question.as_closed().unwrap().answers()[idx].text(),
+                Field::Category => question.category(),
+            };
+            assert!(text[found.range()].eq_ignore_ascii_case("service component"));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+#![cfg(feature = "serde")]
+
+use anyhow::Result;
+use indoc::indoc;
+use md_questions::quiz::QuizSession;
+use md_questions::{ClosedAnswer, Marker, MdQuestions, OpenAnswer, Question};
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::fs::read_to_string;
+
+#[test]
+fn test_serializing_closed_question() -> Result<()> {
+    let question: Question = Question::closed()
+        .number(1)
+        .text("Question 1 text")
+        .answers(vec![
+            ClosedAnswer::incorrect("Answer 1").with_explanation("Because"),
+            ClosedAnswer::correct("Answer 2"),
+        ])
+        .reading(Some("Reading 1".into()))
+        .category("Category 1")
+        .markers(vec![Marker::Hard, Marker::Custom("exam-2023".into())])
+        .tags(vec!["Sling".into()])
+        .build()?
+        .into();
+
+    assert_eq!(
+        serde_json::to_value(&question)?,
+        json!({
+            "kind": "closed",
+            "number": 1,
+            "text": "Question 1 text",
+            "answers": [
+                { "text": "Answer 1", "is_correct": false, "explanation": "Because" },
+                { "text": "Answer 2", "is_correct": true, "explanation": null }
+            ],
+            "explanation": null,
+            "reading": "Reading 1",
+            "category": "Category 1",
+            "markers": ["Hard", "exam-2023"],
+            "tags": ["Sling"],
+            "hints": []
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_deserializing_open_question_without_reading() -> Result<()> {
+    let question: Question = serde_json::from_value(json!({
+        "kind": "open",
+        "number": 2,
+        "text": "Question 2 text",
+        "answer": { "text": "Answer" },
+        "category": "Category 2"
+    }))?;
+
+    assert_eq!(
+        question,
+        Question::open()
+            .number(2)
+            .text("Question 2 text")
+            .answer(OpenAnswer::new("Answer"))
+            .category("Category 2")
+            .build()?
+            .into()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_json_round_trip_of_questions_from_files() -> Result<()> {
+    for path in ["res/closed-questions.md", "res/open-questions.md"] {
+        let questions = MdQuestions::parse(&read_to_string(path)?)?;
+
+        let json = serde_json::to_string(&questions)?;
+
+        assert_eq!(serde_json::from_str::<MdQuestions>(&json)?, questions);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_json_round_trip_keeps_ignored_questions() -> Result<()> {
+    let questions = MdQuestions::parse(indoc! {"
+        ## Question 1 `Category 1` `Ignore`
+        Unfinished
+
+        ---
+
+        ## Question 2 `Category 2`
+        Question 2 text
+
+        ## Answer
+        Answer
+
+        ---
+    "})?;
+    let schema: Value = serde_json::from_str(&read_to_string("schema/questions.schema.json")?)?;
+
+    let json = serde_json::to_value(&questions)?;
+
+    let ignored = &json["ignored"][0];
+    assert_eq!(ignored["position"], 0);
+    assert_eq!(
+        keys(ignored),
+        keys(&schema["$defs"]["ignored_question"]["properties"])
+    );
+    let deserialized = serde_json::from_value::<MdQuestions>(json)?;
+    assert_eq!(deserialized, questions);
+    assert_eq!(deserialized.to_markdown(), questions.to_markdown());
+
+    Ok(())
+}
+
+#[test]
+fn test_schema_describes_serialized_fields() -> Result<()> {
+    let schema: Value = serde_json::from_str(&read_to_string("schema/questions.schema.json")?)?;
+    let content = format!(
+        "{}\n{}",
+        read_to_string("res/closed-questions.md")?,
+        read_to_string("res/open-questions.md")?
+    );
+    let questions = serde_json::to_value(MdQuestions::parse(&content)?)?;
+
+    for question in questions["questions"].as_array().unwrap() {
+        let definition = match question["kind"].as_str() {
+            Some("closed") => &schema["$defs"]["closed_question"],
+            Some("open") => &schema["$defs"]["open_question"],
+            kind => panic!("not supported kind: {kind:?}"),
+        };
+        assert_eq!(keys(question), keys(&definition["properties"]));
+    }
+
+    Ok(())
+}
+
+fn keys(value: &Value) -> BTreeSet<&str> {
+    value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect()
+}
+
+#[test]
+fn test_serializing_lint_report() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let questions = MdQuestions::parse(&content)?;
+
+    let report = questions.lint();
+
+    assert_eq!(
+        serde_json::to_value(&report)?,
+        json!({
+            "diagnostics": [{
+                "severity": "error",
+                "span": {
+                    "start": { "offset": 14507, "line": 364, "column": 1 },
+                    "end": { "offset": 14548, "line": 364, "column": 42 }
+                },
+                "message": "question number 21 is already used",
+                "rule": "duplicate-number"
+            }]
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_serializing_quiz_result() -> Result<()> {
+    let content = read_to_string("res/closed-questions.md")?;
+    let questions = MdQuestions::parse(&content)?;
+    let mut session = QuizSession::new(questions.iter().take(2));
+
+    session.respond(vec![3])?;
+    session.respond(vec![0])?;
+
+    assert_eq!(
+        serde_json::to_value(session.result())?,
+        json!({
+            "questions": [
+                {
+                    "number": 1,
+                    "category": "Templates and Components",
+                    "response": [3],
+                    "score": 1.0
+                },
+                {
+                    "number": 2,
+                    "category": "OSGi Services",
+                    "response": [0],
+                    "score": 0.0
+                }
+            ],
+            "score": 1.0,
+            "max_score": 2.0
+        })
+    );
+
+    Ok(())
+}